@@ -0,0 +1,490 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::{asset::io::file::FileAssetReader, prelude::*};
+use bevy_flappy_macros::hex_to_color;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    AppState, MAX_PLAYERS, Score,
+    components::{ActiveBirds, PlayerSlot},
+    events::{DeathCause, GameplayEvent},
+    highscore::HighScore,
+    level::ActiveLevel,
+    medals::Medal,
+};
+use crate::input::{ActionState, GameAction};
+use crate::locale::Phrase;
+use crate::profile::{ActiveProfile, Profile};
+use crate::settings::UserSettings;
+use crate::stats::Stats;
+use crate::storage;
+use crate::ui::toast::Toast;
+
+pub const ACHIEVEMENTS_FILE: &str = "achievements.ron";
+// Achievement definitions, read from the assets directory
+const ACHIEVEMENT_LIST_FILE: &str = "achievements.ron";
+// Birds below this height have dropped under the middle of the screen
+const MIDLINE_Y: f32 = 0.0;
+
+const MENU_BG_COLOR: Color = hex_to_color!("#e4ede6");
+const BUTTON_COLOR_IDLE: Color = hex_to_color!("#c3d8d2");
+const BUTTON_COLOR_HOVER: Color = hex_to_color!("#f4f5f4");
+const BUTTON_COLOR_PRESSED: Color = hex_to_color!("#c3d8d2");
+const TEXT_COLOR: Color = hex_to_color!("#2f3e46");
+const CARD_LOCKED_COLOR: Color = hex_to_color!("#d5dfd9");
+const CARD_UNLOCKED_COLOR: Color = hex_to_color!("#f6e7a8");
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub enum Condition {
+    // Score in a single run
+    Score(u32),
+    // Pipes in a row in a single run without the bird dropping below the midline
+    PipesAboveMidline(u32),
+    GamesPlayed(u32),
+    PipesPassed(u64),
+    Flaps(u64),
+    Coins(u64),
+    Deaths(DeathCause, u32),
+    // A medal of this kind or better
+    Medal(Medal),
+    DailyStreak(u32),
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct Achievement {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub condition: Condition,
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct AchievementRegistry {
+    pub achievements: Vec<Achievement>,
+}
+
+impl Default for AchievementRegistry {
+    fn default() -> Self {
+        Self {
+            achievements: load_achievement_list(),
+        }
+    }
+}
+
+fn load_achievement_list() -> Vec<Achievement> {
+    let path = FileAssetReader::get_base_path()
+        .join("assets")
+        .join(ACHIEVEMENT_LIST_FILE);
+    let Ok(contents) = fs::read_to_string(&path) else {
+        warn!("No achievements defined: {} is missing", path.display());
+        return Vec::new();
+    };
+    match ron::from_str(&contents) {
+        Ok(achievements) => achievements,
+        Err(err) => {
+            warn!("Invalid {}: {err}", path.display());
+            Vec::new()
+        }
+    }
+}
+
+// Everything conditions are evaluated against
+pub struct AchievementContext<'a> {
+    pub stats: &'a Stats,
+    pub profile: &'a Profile,
+    pub best_score: u32,
+    pub best_midline_run: u32,
+}
+
+impl Condition {
+    // Current value and target, or `None` for conditions that only a single run can meet
+    pub fn progress(&self, context: &AchievementContext) -> Option<(u64, u64)> {
+        let stats = context.stats;
+        let progress = match *self {
+            Self::Score(target) => (context.best_score.into(), target.into()),
+            Self::PipesAboveMidline(_) => return None,
+            Self::GamesPlayed(target) => (stats.games_played.into(), target.into()),
+            Self::PipesPassed(target) => (stats.pipes_passed, target),
+            Self::Flaps(target) => (stats.flaps, target),
+            Self::Coins(target) => (stats.coins, target),
+            Self::Deaths(cause, target) => {
+                let deaths = match cause {
+                    DeathCause::TopPipe => stats.deaths.top_pipe,
+                    DeathCause::BottomPipe => stats.deaths.bottom_pipe,
+                    DeathCause::Ground => stats.deaths.ground,
+                };
+                (deaths.into(), target.into())
+            }
+            Self::Medal(medal) => {
+                let earned = context
+                    .profile
+                    .medals
+                    .iter()
+                    .any(|(earned, count)| *earned >= medal && *count > 0);
+                (u64::from(earned), 1)
+            }
+            Self::DailyStreak(target) => (stats.best_streak.into(), target.into()),
+        };
+        Some(progress)
+    }
+
+    pub fn met(&self, context: &AchievementContext) -> bool {
+        match *self {
+            Self::PipesAboveMidline(target) => context.best_midline_run >= target,
+            _ => self
+                .progress(context)
+                .is_some_and(|(current, target)| current >= target),
+        }
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct Achievements {
+    // Achievement id to the Unix time it was unlocked
+    pub unlocked: BTreeMap<String, u64>,
+}
+
+impl Achievements {
+    pub fn load(profile: &str) -> Self {
+        storage::load(storage::profile_path(profile, ACHIEVEMENTS_FILE)).unwrap_or_default()
+    }
+
+    pub fn save(&self, profile: &str) {
+        if let Err(err) = storage::save(storage::profile_path(profile, ACHIEVEMENTS_FILE), self) {
+            warn!("Failed to save achievements: {err}");
+        }
+    }
+
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.contains_key(id)
+    }
+}
+
+// Pipes each bird has passed since it last dropped below the midline
+#[derive(Resource, Default)]
+pub struct MidlineRun {
+    pub streaks: [u32; MAX_PLAYERS],
+    pub best: u32,
+}
+
+type QueryButton<'w, 's, 'a> = Query<
+    'w,
+    's,
+    (
+        &'a Interaction,
+        &'a AchievementsOption,
+        &'a mut BackgroundColor,
+    ),
+    (Changed<Interaction>, With<Button>),
+>;
+
+#[derive(Component)]
+pub struct AchievementsMenu;
+
+#[derive(Component, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum AchievementsOption {
+    Back,
+}
+
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AchievementRegistry>()
+            .init_resource::<Achievements>()
+            .init_resource::<MidlineRun>()
+            .add_systems(OnEnter(AppState::InGame), reset_midline_run)
+            .add_systems(
+                PostUpdate,
+                (
+                    track_midline_run.run_if(in_state(AppState::InGame)),
+                    unlock_achievements,
+                )
+                    .chain(),
+            )
+            .add_systems(OnEnter(AppState::Achievements), setup)
+            .add_systems(
+                Update,
+                handle_input.run_if(in_state(AppState::Achievements)),
+            )
+            .add_systems(OnExit(AppState::Achievements), cleanup)
+            .add_systems(Update, save_achievements);
+    }
+}
+
+fn save_achievements(achievements: Res<Achievements>, active: Res<ActiveProfile>) {
+    if achievements.is_changed() && !achievements.is_added() {
+        achievements.save(&active.0);
+    }
+}
+
+fn reset_midline_run(mut midline_run: ResMut<MidlineRun>) {
+    *midline_run = MidlineRun::default();
+}
+
+fn track_midline_run(
+    mut gameplay_events: EventReader<GameplayEvent>,
+    active_level: Res<ActiveLevel>,
+    bird_query: ActiveBirds<(&Transform, &PlayerSlot)>,
+    mut midline_run: ResMut<MidlineRun>,
+) {
+    for (transform, slot) in &bird_query {
+        if transform.translation.y < MIDLINE_Y && midline_run.streaks[slot.0] > 0 {
+            midline_run.streaks[slot.0] = 0;
+        }
+    }
+
+    for event in gameplay_events.read() {
+        if let GameplayEvent::PipePassed { slot } = event
+            && !active_level.playtest
+        {
+            midline_run.streaks[*slot] += 1;
+            midline_run.best = midline_run.best.max(midline_run.streaks[*slot]);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn unlock_achievements(
+    stats: Res<Stats>,
+    profile: Res<Profile>,
+    high_score: Res<HighScore>,
+    score: Res<Score>,
+    active_level: Res<ActiveLevel>,
+    midline_run: Res<MidlineRun>,
+    settings: Res<UserSettings>,
+    registry: Res<AchievementRegistry>,
+    mut achievements: ResMut<Achievements>,
+    mut toasts: EventWriter<Toast>,
+) {
+    let changed = stats.is_changed()
+        || profile.is_changed()
+        || high_score.is_changed()
+        || score.is_changed()
+        || midline_run.is_changed();
+    if !changed {
+        return;
+    }
+
+    // Runs from the editor and precision-scored runs don't count towards the score
+    // achievements
+    let run_score = if active_level.playtest || settings.precision_scoring {
+        0
+    } else {
+        score.0
+    };
+    let context = AchievementContext {
+        stats: &stats,
+        profile: &profile,
+        best_score: high_score.best.max(run_score),
+        best_midline_run: midline_run.best,
+    };
+
+    for achievement in &registry.achievements {
+        if achievements.is_unlocked(&achievement.id) || !achievement.condition.met(&context) {
+            continue;
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        achievements
+            .unlocked
+            .insert(achievement.id.clone(), timestamp);
+        toasts.write(Toast {
+            title: settings
+                .language
+                .translate(Phrase::AchievementUnlocked)
+                .to_string(),
+            message: achievement.name.clone(),
+        });
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    achievements: Res<Achievements>,
+    registry: Res<AchievementRegistry>,
+    stats: Res<Stats>,
+    profile: Res<Profile>,
+    high_score: Res<HighScore>,
+    settings: Res<UserSettings>,
+) {
+    let language = settings.language;
+    let context = AchievementContext {
+        stats: &stats,
+        profile: &profile,
+        best_score: high_score.best,
+        best_midline_run: 0,
+    };
+    let unlocked = registry
+        .achievements
+        .iter()
+        .filter(|achievement| achievements.is_unlocked(&achievement.id))
+        .count();
+
+    let root = commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(MENU_BG_COLOR),
+            AchievementsMenu,
+        ))
+        .id();
+
+    commands.entity(root).with_children(|parent| {
+        parent.spawn((
+            Text(language.fill(
+                Phrase::AchievementCount,
+                &[&unlocked, &registry.achievements.len()],
+            )),
+            TextFont {
+                font_size: 28.0,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+        ));
+
+        // Two cards per row
+        parent
+            .spawn(Node {
+                width: Val::Percent(96.0),
+                flex_wrap: FlexWrap::Wrap,
+                justify_content: JustifyContent::Center,
+                margin: UiRect::vertical(Val::Px(8.0)),
+                ..default()
+            })
+            .with_children(|grid| {
+                for achievement in &registry.achievements {
+                    let unlocked = achievements.is_unlocked(&achievement.id);
+                    let state = if unlocked {
+                        language.translate(Phrase::Unlocked).to_string()
+                    } else {
+                        match achievement.condition.progress(&context) {
+                            Some((current, target)) if target > 1 => {
+                                format!("{}/{target}", current.min(target))
+                            }
+                            _ => language.translate(Phrase::Locked).to_string(),
+                        }
+                    };
+
+                    grid.spawn((
+                        Node {
+                            width: Val::Percent(48.0),
+                            margin: UiRect::all(Val::Px(3.0)),
+                            padding: UiRect::all(Val::Px(5.0)),
+                            flex_direction: FlexDirection::Column,
+                            ..default()
+                        },
+                        BorderRadius::all(Val::Px(6.0)),
+                        BackgroundColor(if unlocked {
+                            CARD_UNLOCKED_COLOR
+                        } else {
+                            CARD_LOCKED_COLOR
+                        }),
+                        children![
+                            (
+                                Text(format!("{} - {state}", achievement.name)),
+                                TextFont {
+                                    font_size: 14.0,
+                                    ..default()
+                                },
+                                TextColor(TEXT_COLOR),
+                            ),
+                            (
+                                Text(achievement.description.clone()),
+                                TextFont {
+                                    font_size: 11.0,
+                                    ..default()
+                                },
+                                TextColor(TEXT_COLOR),
+                            ),
+                        ],
+                    ));
+                }
+            });
+
+        parent.spawn((
+            Node {
+                width: Val::Px(180.0),
+                height: Val::Px(30.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(BUTTON_COLOR_IDLE),
+            Button,
+            AchievementsOption::Back,
+            children![(
+                Text(language.translate(Phrase::Back).to_string()),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+            )],
+        ));
+    });
+}
+
+fn cleanup(mut commands: Commands, query: Query<Entity, With<AchievementsMenu>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn handle_input(
+    mut interaction_query: QueryButton,
+    actions: Res<ActionState>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    if actions.just_pressed(GameAction::Back) {
+        app_state.set(AppState::Stats);
+    }
+
+    for (interaction, option, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                match option {
+                    AchievementsOption::Back => {
+                        app_state.set(AppState::Stats);
+                    }
+                }
+                *bg_color = BackgroundColor(BUTTON_COLOR_PRESSED);
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(BUTTON_COLOR_HOVER);
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(BUTTON_COLOR_IDLE);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_achievements_parse() {
+        let achievements: Vec<Achievement> =
+            ron::from_str(include_str!("../assets/achievements.ron")).unwrap();
+        assert_eq!(achievements.len(), 12);
+        let mut ids: Vec<&str> = achievements
+            .iter()
+            .map(|achievement| achievement.id.as_str())
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), achievements.len());
+    }
+}
@@ -0,0 +1,338 @@
+use bevy::prelude::*;
+use bevy_flappy_macros::hex_to_color;
+
+use crate::game::AppState;
+use crate::input::{ActionState, Binding, GameAction, InputBindings};
+use crate::locale::Phrase;
+use crate::settings::UserSettings;
+use crate::ui::navigation::FocusedButton;
+
+const MENU_BG_COLOR: Color = hex_to_color!("#e4ede6");
+const BUTTON_COLOR_IDLE: Color = hex_to_color!("#c3d8d2");
+const BUTTON_COLOR_HOVER: Color = hex_to_color!("#f4f5f4");
+const BUTTON_COLOR_PRESSED: Color = hex_to_color!("#c3d8d2");
+const BUTTON_COLOR_CAPTURE: Color = hex_to_color!("#f6d776");
+const TEXT_COLOR: Color = hex_to_color!("#2f3e46");
+
+const SLOTS_PER_ACTION: usize = 4;
+const EMPTY_SLOT_LABEL: &str = "+";
+
+type QueryButton<'w, 's, 'a> = Query<
+    'w,
+    's,
+    (&'a Interaction, &'a ControlsOption, &'a mut BackgroundColor),
+    (Changed<Interaction>, With<Button>),
+>;
+
+#[derive(Component)]
+pub struct ControlsMenu;
+
+#[derive(Component)]
+pub struct ControlsStatusText;
+
+#[derive(Component, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ControlsOption {
+    Slot(GameAction, usize),
+    Reset,
+    Back,
+}
+
+// Binding slot waiting for the next key, mouse or gamepad button
+#[derive(Resource, Default)]
+pub struct RebindCapture {
+    pub target: Option<(GameAction, usize)>,
+    pub status: String,
+    // Skips the frame the capture started on so the click that started it isn't bound
+    ready: bool,
+}
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RebindCapture>()
+            .add_systems(OnEnter(AppState::Controls), setup)
+            .add_systems(
+                Update,
+                // Input runs first so the Escape that cancels a capture doesn't also leave the screen
+                (handle_input, capture_binding, update_labels)
+                    .chain()
+                    .run_if(in_state(AppState::Controls)),
+            )
+            .add_systems(OnExit(AppState::Controls), cleanup);
+    }
+}
+
+fn setup(mut commands: Commands, mut capture: ResMut<RebindCapture>, settings: Res<UserSettings>) {
+    fn create_button(text: &str, option: ControlsOption, width: Val) -> impl Bundle {
+        (
+            Node {
+                width,
+                height: Val::Px(26.0),
+                margin: UiRect::all(Val::Px(2.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(BUTTON_COLOR_IDLE),
+            Button,
+            option,
+            children![(
+                Text(text.to_string()),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+            )],
+        )
+    }
+
+    let language = settings.language;
+    *capture = RebindCapture::default();
+
+    let root = commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(MENU_BG_COLOR),
+            ControlsMenu,
+        ))
+        .id();
+
+    commands.entity(root).with_children(|parent| {
+        parent.spawn((
+            Text(language.translate(Phrase::Controls).to_string()),
+            TextFont {
+                font_size: 28.0,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+        ));
+
+        for action in GameAction::ALL {
+            parent
+                .spawn(Node {
+                    align_items: AlignItems::Center,
+                    ..default()
+                })
+                .with_children(|row| {
+                    row.spawn((
+                        Node {
+                            width: Val::Px(80.0),
+                            ..default()
+                        },
+                        Text(language.translate(action.phrase()).to_string()),
+                        TextFont {
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(TEXT_COLOR),
+                    ));
+                    for slot in 0..SLOTS_PER_ACTION {
+                        row.spawn(create_button(
+                            EMPTY_SLOT_LABEL,
+                            ControlsOption::Slot(action, slot),
+                            Val::Px(130.0),
+                        ));
+                    }
+                });
+        }
+
+        parent.spawn((
+            ControlsStatusText,
+            Text::default(),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+        ));
+        parent.spawn((
+            Node::default(),
+            children![
+                create_button(
+                    language.translate(Phrase::ResetToDefaults),
+                    ControlsOption::Reset,
+                    Val::Px(160.0),
+                ),
+                create_button(
+                    language.translate(Phrase::Back),
+                    ControlsOption::Back,
+                    Val::Px(160.0),
+                ),
+            ],
+        ));
+    });
+}
+
+fn cleanup(
+    mut commands: Commands,
+    mut focused: ResMut<FocusedButton>,
+    query: Query<Entity, With<ControlsMenu>>,
+) {
+    focused.locked = false;
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn capture_binding(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    gamepads: Query<&Gamepad>,
+    mut capture: ResMut<RebindCapture>,
+    mut bindings: ResMut<InputBindings>,
+    mut focused: ResMut<FocusedButton>,
+    settings: Res<UserSettings>,
+) {
+    let Some((action, slot)) = capture.target else {
+        return;
+    };
+    let language = settings.language;
+    let action_name = language.translate(action.phrase());
+    if !capture.ready {
+        capture.ready = true;
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::Escape) {
+        capture.target = None;
+        capture.status = language.translate(Phrase::Cancelled).to_string();
+        focused.locked = false;
+        return;
+    }
+    if keyboard.any_just_pressed([KeyCode::Backspace, KeyCode::Delete]) {
+        bindings.set(action, slot, None);
+        capture.target = None;
+        capture.status = language.fill(Phrase::ClearedBinding, &[&action_name]);
+        focused.locked = false;
+        return;
+    }
+
+    let binding = keyboard
+        .get_just_pressed()
+        .next()
+        .map(|key| Binding::Key(*key))
+        .or_else(|| mouse.get_just_pressed().next().map(|b| Binding::Mouse(*b)))
+        .or_else(|| touches.any_just_pressed().then_some(Binding::Touch))
+        .or_else(|| {
+            gamepads
+                .iter()
+                .find_map(|gamepad| gamepad.get_just_pressed().next().copied())
+                .map(Binding::Gamepad)
+        });
+    let Some(binding) = binding else {
+        return;
+    };
+
+    capture.target = None;
+    focused.locked = false;
+
+    if let Some(other) = bindings.conflict(action, binding) {
+        let other_name = language.translate(other.phrase());
+        capture.status = language.fill(Phrase::AlreadyBound, &[&binding.label(), &other_name]);
+        return;
+    }
+    if bindings.get(action).contains(&binding) {
+        capture.status = language.fill(Phrase::AlreadyTriggers, &[&binding.label(), &action_name]);
+        return;
+    }
+
+    bindings.set(action, slot, Some(binding));
+    capture.status = language.fill(Phrase::NowTriggers, &[&binding.label(), &action_name]);
+}
+
+fn handle_input(
+    mut interaction_query: QueryButton,
+    actions: Res<ActionState>,
+    mut capture: ResMut<RebindCapture>,
+    mut bindings: ResMut<InputBindings>,
+    mut focused: ResMut<FocusedButton>,
+    settings: Res<UserSettings>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    let language = settings.language;
+    if capture.target.is_none() && actions.just_pressed(GameAction::Back) {
+        app_state.set(AppState::Settings);
+    }
+
+    for (interaction, option, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                match option {
+                    ControlsOption::Slot(action, slot) => {
+                        capture.target = Some((*action, *slot));
+                        capture.ready = false;
+                        let action_name = language.translate(action.phrase());
+                        capture.status = language.fill(Phrase::PressBinding, &[&action_name]);
+                        focused.locked = true;
+                    }
+                    ControlsOption::Reset => {
+                        *bindings = InputBindings::default();
+                        capture.status = language.translate(Phrase::ControlsReset).to_string();
+                    }
+                    ControlsOption::Back => {
+                        app_state.set(AppState::Settings);
+                    }
+                }
+                *bg_color = BackgroundColor(BUTTON_COLOR_PRESSED);
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(BUTTON_COLOR_HOVER);
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(BUTTON_COLOR_IDLE);
+            }
+        }
+    }
+}
+
+fn update_labels(
+    bindings: Res<InputBindings>,
+    capture: Res<RebindCapture>,
+    mut slot_query: Query<(&ControlsOption, &Children, &mut BackgroundColor)>,
+    mut text_query: Query<&mut Text, Without<ControlsStatusText>>,
+    status_query: Single<(&mut Text, Ref<ControlsStatusText>)>,
+) {
+    let (mut status_text, status_marker) = status_query.into_inner();
+    if !bindings.is_changed() && !capture.is_changed() && !status_marker.is_added() {
+        return;
+    }
+    status_text.0 = capture.status.clone();
+
+    for (option, children, mut bg_color) in &mut slot_query {
+        let ControlsOption::Slot(action, slot) = *option else {
+            continue;
+        };
+
+        let capturing = capture.target == Some((action, slot));
+        let label = if capturing {
+            "...".to_string()
+        } else {
+            bindings
+                .get(action)
+                .get(slot)
+                .map(Binding::label)
+                .unwrap_or_else(|| EMPTY_SLOT_LABEL.to_string())
+        };
+
+        if capturing {
+            *bg_color = BackgroundColor(BUTTON_COLOR_CAPTURE);
+        } else if bg_color.0 == BUTTON_COLOR_CAPTURE {
+            *bg_color = BackgroundColor(BUTTON_COLOR_IDLE);
+        }
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0.clone_from(&label);
+            }
+        }
+    }
+}
@@ -0,0 +1,568 @@
+use bevy::{
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+        mouse::AccumulatedMouseScroll,
+    },
+    prelude::*,
+    window::PrimaryWindow,
+};
+
+use crate::game::{
+    AppState, BG_IMG_DIMENSIONS, GameWorld, PIPE_HEIGHT, PIPE_WIDTH, PipeTextures, spawn_world,
+    level::*,
+    pipes::spawn_pipe_pair,
+    skins::SkinRegistry,
+};
+use crate::input::{ActionState, GameAction};
+use crate::locale::Phrase;
+use crate::profile::Profile;
+use crate::settings::UserSettings;
+
+const SCROLL_SPEED: f32 = 300.0;
+const WHEEL_SCROLL_STEP: f32 = 40.0;
+const GAP_STEP: f32 = 10.0;
+const MIN_EDITOR_GAP: f32 = 80.0;
+const MAX_EDITOR_GAP: f32 = 300.0;
+const DEFAULT_EDITOR_GAP: f32 = 180.0;
+const MAX_LEVEL_NAME_LEN: usize = 24;
+// Saved levels shown at once in the open list
+const LEVEL_LIST_ROWS: usize = 6;
+
+// Text entry or list that takes over the keyboard until it's confirmed or cancelled
+#[derive(Default)]
+pub enum EditorPrompt {
+    #[default]
+    None,
+    Rename(String),
+    Open {
+        levels: Vec<String>,
+        index: usize,
+    },
+}
+
+#[derive(Resource)]
+pub struct EditorState {
+    pub level: Level,
+    pub scroll: f32,
+    pub selected: Option<usize>,
+    pub dragging: bool,
+    pub dirty: bool,
+    pub status: String,
+    pub prompt: EditorPrompt,
+}
+
+impl Default for EditorState {
+    fn default() -> Self {
+        Self {
+            level: Level {
+                name: DEFAULT_LEVEL_NAME.to_string(),
+                pipes: Vec::new(),
+            },
+            scroll: 0.0,
+            selected: None,
+            dragging: false,
+            dirty: true,
+            status: String::new(),
+            prompt: EditorPrompt::None,
+        }
+    }
+}
+
+impl EditorState {
+    fn pipe_at(&self, cursor: Vec2) -> Option<usize> {
+        self.level
+            .pipes
+            .iter()
+            .position(|spec| (spec.x - self.scroll - cursor.x).abs() < PIPE_WIDTH / 2.0)
+    }
+
+    fn selected_mut(&mut self) -> Option<&mut PipeSpec> {
+        self.selected.and_then(|index| self.level.pipes.get_mut(index))
+    }
+}
+
+#[derive(Component)]
+pub struct EditorUi;
+
+#[derive(Component)]
+pub struct EditorStatusText;
+
+#[derive(Component)]
+pub struct EditorPipe(pub usize);
+
+pub struct EditorPlugin;
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EditorState>()
+            .add_systems(OnEnter(AppState::Editor), setup)
+            .add_systems(OnEnter(AppState::MainMenu), clear_active_level)
+            .add_systems(
+                Update,
+                (
+                    (scroll_editor, handle_mouse, handle_keys).run_if(no_prompt),
+                    handle_prompt,
+                    sync_editor_pipes,
+                    draw_gizmos,
+                    update_status,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Editor)),
+            )
+            .add_systems(OnExit(AppState::Editor), cleanup);
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    skins: Res<SkinRegistry>,
+    settings: Res<UserSettings>,
+    profile: Res<Profile>,
+    mut editor: ResMut<EditorState>,
+) {
+    commands.insert_resource(PipeTextures::load(&asset_server));
+    let skin = skins.get(profile.skin(&settings));
+    let bird_textures = skin.load_textures(&asset_server);
+    spawn_world(
+        &mut commands,
+        &asset_server,
+        bird_textures.mid,
+        skin.timing,
+        1,
+        profile.background_path(),
+    );
+
+    commands.spawn((
+        EditorUi,
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::SpaceBetween,
+            padding: UiRect::all(Val::Px(10.0)),
+            ..default()
+        },
+        children![
+            (
+                Text(settings.language.translate(Phrase::EditorHelp).to_string()),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextShadow::default(),
+            ),
+            (
+                EditorStatusText,
+                Text::default(),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextShadow::default(),
+            ),
+        ],
+    ));
+
+    editor.dragging = false;
+    editor.dirty = true;
+    editor.prompt = EditorPrompt::None;
+}
+
+fn no_prompt(editor: Res<EditorState>) -> bool {
+    matches!(editor.prompt, EditorPrompt::None)
+}
+
+fn clear_active_level(mut commands: Commands) {
+    commands.insert_resource(ActiveLevel::default());
+}
+
+fn cursor_world_position(
+    window: &Window,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+) -> Option<Vec2> {
+    let cursor = window.cursor_position()?;
+    camera.viewport_to_world_2d(camera_transform, cursor).ok()
+}
+
+fn clamp_gap_y(gap_y: f32, gap_size: f32) -> f32 {
+    let limit = BG_IMG_DIMENSIONS.1 / 2.0 - gap_size / 2.0;
+    gap_y.clamp(-limit, limit)
+}
+
+fn scroll_editor(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse_scroll: Res<AccumulatedMouseScroll>,
+    time: Res<Time>,
+    mut editor: ResMut<EditorState>,
+) {
+    let mut delta = 0.0;
+    if keyboard.any_pressed([KeyCode::KeyA, KeyCode::ArrowLeft]) {
+        delta -= SCROLL_SPEED * time.delta_secs();
+    }
+    if keyboard.any_pressed([KeyCode::KeyD, KeyCode::ArrowRight]) {
+        delta += SCROLL_SPEED * time.delta_secs();
+    }
+    delta -= mouse_scroll.delta.y * WHEEL_SCROLL_STEP;
+
+    if delta != 0.0 {
+        editor.scroll = (editor.scroll + delta).max(0.0);
+    }
+}
+
+fn handle_mouse(
+    mouse: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    mut editor: ResMut<EditorState>,
+) {
+    let (camera, camera_transform) = camera.into_inner();
+    let Some(cursor) = cursor_world_position(&window, camera, camera_transform) else {
+        return;
+    };
+
+    if mouse.just_pressed(MouseButton::Left) {
+        let index = match editor.pipe_at(cursor) {
+            Some(index) => index,
+            None => {
+                let spec = PipeSpec {
+                    x: cursor.x + editor.scroll,
+                    gap_y: clamp_gap_y(cursor.y, DEFAULT_EDITOR_GAP),
+                    gap_size: DEFAULT_EDITOR_GAP,
+                    variant: PipeVariant::Green,
+                    movement: PipeMovement::Static,
+                };
+                editor.level.pipes.push(spec);
+                editor.dirty = true;
+                editor.level.pipes.len() - 1
+            }
+        };
+        editor.selected = Some(index);
+        editor.dragging = true;
+    } else if mouse.pressed(MouseButton::Left)
+        && editor.dragging
+        && let Some(spec) = editor.selected_mut()
+    {
+        spec.gap_y = clamp_gap_y(cursor.y, spec.gap_size);
+        editor.dirty = true;
+    }
+
+    if mouse.just_released(MouseButton::Left) {
+        editor.dragging = false;
+    }
+
+    if mouse.just_pressed(MouseButton::Right)
+        && let Some(index) = editor.pipe_at(cursor)
+    {
+        editor.level.pipes.remove(index);
+        editor.selected = None;
+        editor.dirty = true;
+    }
+}
+
+fn handle_keys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
+    mut editor: ResMut<EditorState>,
+    mut active_level: ResMut<ActiveLevel>,
+    settings: Res<UserSettings>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    let language = settings.language;
+    let ctrl = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+
+    if actions.just_pressed(GameAction::Back) {
+        app_state.set(AppState::MainMenu);
+        return;
+    }
+
+    if ctrl && keyboard.just_pressed(KeyCode::KeyS) {
+        editor.level.sort();
+        editor.selected = None;
+        editor.dirty = true;
+        editor.status = match editor.level.save() {
+            Ok(()) => language.fill(Phrase::LevelSaved, &[&editor.level.name]),
+            Err(err) => language.fill(Phrase::SaveFailed, &[&err]),
+        };
+        return;
+    }
+
+    if ctrl && keyboard.just_pressed(KeyCode::KeyO) {
+        let levels = Level::list();
+        if levels.is_empty() {
+            editor.status = language.translate(Phrase::NoSavedLevels).to_string();
+        } else {
+            let index = levels
+                .iter()
+                .position(|name| *name == editor.level.name)
+                .unwrap_or(0);
+            editor.prompt = EditorPrompt::Open { levels, index };
+        }
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::F2) {
+        editor.prompt = EditorPrompt::Rename(editor.level.name.clone());
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyP) {
+        let mut level = editor.level.clone();
+        level.sort();
+        *active_level = ActiveLevel {
+            level: Some(level),
+            start_distance: editor.scroll,
+            playtest: true,
+        };
+        app_state.set(AppState::InGame);
+        return;
+    }
+
+    if keyboard.any_just_pressed([KeyCode::Delete, KeyCode::Backspace]) {
+        if let Some(index) = editor.selected.take() {
+            editor.level.pipes.remove(index);
+            editor.dirty = true;
+        }
+        return;
+    }
+
+    let Some(spec) = editor.selected_mut() else {
+        return;
+    };
+    let mut changed = false;
+
+    if keyboard.just_pressed(KeyCode::BracketLeft) {
+        spec.gap_size = (spec.gap_size - GAP_STEP).max(MIN_EDITOR_GAP);
+        changed = true;
+    }
+    if keyboard.just_pressed(KeyCode::BracketRight) {
+        spec.gap_size = (spec.gap_size + GAP_STEP).min(MAX_EDITOR_GAP);
+        changed = true;
+    }
+    if keyboard.just_pressed(KeyCode::KeyV) {
+        spec.variant = match spec.variant {
+            PipeVariant::Green => PipeVariant::Red,
+            PipeVariant::Red => PipeVariant::Green,
+        };
+        changed = true;
+    }
+    if keyboard.just_pressed(KeyCode::KeyM) {
+        spec.movement = match spec.movement {
+            PipeMovement::Static => PipeMovement::Oscillate {
+                amplitude: 40.0,
+                speed: 1.5,
+            },
+            PipeMovement::Oscillate { amplitude, .. } if amplitude < 80.0 => {
+                PipeMovement::Oscillate {
+                    amplitude: 80.0,
+                    speed: 2.5,
+                }
+            }
+            PipeMovement::Oscillate { .. } => PipeMovement::Static,
+        };
+        changed = true;
+    }
+
+    if changed {
+        spec.gap_y = clamp_gap_y(spec.gap_y, spec.gap_size);
+        editor.dirty = true;
+    }
+}
+
+// Names become file names, so they're kept to letters, digits, dashes and underscores
+fn handle_prompt(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    actions: Res<ActionState>,
+    mut editor: ResMut<EditorState>,
+    settings: Res<UserSettings>,
+) {
+    let language = settings.language;
+    let cancel = actions.just_pressed(GameAction::Back);
+    let confirm = actions.just_pressed(GameAction::Confirm);
+
+    match &mut editor.prompt {
+        EditorPrompt::None => {
+            keyboard_events.clear();
+        }
+        EditorPrompt::Rename(name) => {
+            for event in keyboard_events.read() {
+                if event.state != ButtonState::Pressed {
+                    continue;
+                }
+                match &event.logical_key {
+                    Key::Character(chars) => {
+                        for c in chars
+                            .chars()
+                            .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+                        {
+                            if name.len() < MAX_LEVEL_NAME_LEN {
+                                name.push(c);
+                            }
+                        }
+                    }
+                    Key::Backspace => {
+                        name.pop();
+                    }
+                    _ => {}
+                }
+            }
+
+            if confirm && !name.is_empty() {
+                let name = name.clone();
+                editor.status = language.fill(Phrase::LevelRenamed, &[&name]);
+                editor.level.name = name;
+                editor.prompt = EditorPrompt::None;
+            } else if cancel {
+                editor.prompt = EditorPrompt::None;
+            }
+        }
+        EditorPrompt::Open { levels, index } => {
+            keyboard_events.clear();
+            if actions.just_pressed(GameAction::NavigateUp) {
+                *index = index.saturating_sub(1);
+            }
+            if actions.just_pressed(GameAction::NavigateDown) {
+                *index = (*index + 1).min(levels.len() - 1);
+            }
+
+            if confirm {
+                let name = levels[*index].clone();
+                match Level::load(&name) {
+                    Some(mut level) => {
+                        // The file name is the level's name, even if the file was renamed
+                        level.name.clone_from(&name);
+                        editor.level = level;
+                        editor.selected = None;
+                        editor.scroll = 0.0;
+                        editor.dirty = true;
+                        editor.status = language.fill(Phrase::LevelLoaded, &[&name]);
+                    }
+                    None => editor.status = language.fill(Phrase::LevelUnreadable, &[&name]),
+                }
+                editor.prompt = EditorPrompt::None;
+            } else if cancel {
+                editor.prompt = EditorPrompt::None;
+            }
+        }
+    }
+}
+
+fn sync_editor_pipes(
+    mut commands: Commands,
+    pipe_textures: Res<PipeTextures>,
+    mut editor: ResMut<EditorState>,
+    root_query: Single<Entity, With<GameWorld>>,
+    mut pipe_query: Query<(Entity, &EditorPipe, &mut Transform)>,
+) {
+    if !editor.dirty {
+        // Only the scroll position changed, so shift the existing pipes
+        for (_, editor_pipe, mut transform) in &mut pipe_query {
+            if let Some(spec) = editor.level.pipes.get(editor_pipe.0) {
+                transform.translation.x = spec.x - editor.scroll;
+            }
+        }
+        return;
+    }
+
+    for (entity, _, _) in &pipe_query {
+        commands.entity(entity).despawn();
+    }
+
+    let root = root_query.into_inner();
+    commands.entity(root).with_children(|parent| {
+        for (index, spec) in editor.level.pipes.iter().enumerate() {
+            let entity = spawn_pipe_pair(parent, &pipe_textures, spec.x - editor.scroll, spec);
+            parent.commands().entity(entity).insert(EditorPipe(index));
+        }
+    });
+    editor.dirty = false;
+}
+
+fn draw_gizmos(editor: Res<EditorState>, mut gizmos: Gizmos) {
+    for (index, spec) in editor.level.pipes.iter().enumerate() {
+        let center = Vec2::new(spec.x - editor.scroll, spec.gap_y);
+        let color = if editor.selected == Some(index) {
+            Color::srgb(1.0, 0.9, 0.2)
+        } else {
+            Color::srgba(1.0, 1.0, 1.0, 0.4)
+        };
+        gizmos.rect_2d(center, Vec2::new(PIPE_WIDTH, spec.gap_size), color);
+
+        if let PipeMovement::Oscillate { amplitude, .. } = spec.movement {
+            let extent = Vec2::new(0.0, amplitude);
+            gizmos.line_2d(center - extent, center + extent, color);
+        }
+    }
+
+    // Course start marker
+    let start_x = -editor.scroll;
+    gizmos.line_2d(
+        Vec2::new(start_x, -PIPE_HEIGHT),
+        Vec2::new(start_x, PIPE_HEIGHT),
+        Color::srgb(0.2, 1.0, 0.4),
+    );
+}
+
+fn update_status(
+    editor: Res<EditorState>,
+    settings: Res<UserSettings>,
+    status_query: Single<&mut Text, With<EditorStatusText>>,
+) {
+    let language = settings.language;
+    let mut text = status_query.into_inner();
+
+    let selected = match editor.selected.and_then(|index| editor.level.pipes.get(index)) {
+        Some(spec) => language.fill(
+            Phrase::SelectedPipe,
+            &[
+                &spec.x.round(),
+                &spec.gap_size.round(),
+                &spec.gap_y.round(),
+                &format!("{:?}", spec.variant),
+                &format!("{:?}", spec.movement),
+            ],
+        ),
+        None => language.translate(Phrase::SelectedNone).to_string(),
+    };
+
+    let footer = match &editor.prompt {
+        EditorPrompt::None => editor.status.clone(),
+        EditorPrompt::Rename(name) => language.fill(Phrase::LevelNamePrompt, &[name]),
+        EditorPrompt::Open { levels, index } => {
+            let first = index.saturating_sub(LEVEL_LIST_ROWS - 1);
+            let rows: Vec<String> = levels
+                .iter()
+                .enumerate()
+                .skip(first)
+                .take(LEVEL_LIST_ROWS)
+                .map(|(i, name)| {
+                    let marker = if i == *index { ">" } else { " " };
+                    format!("{marker} {name}")
+                })
+                .collect();
+            format!(
+                "{}\n{}",
+                language.translate(Phrase::OpenLevelPrompt),
+                rows.join("\n")
+            )
+        }
+    };
+
+    let summary = language.fill(
+        Phrase::EditorSummary,
+        &[&editor.level.name, &editor.level.pipes.len(), &editor.scroll.round()],
+    );
+    **text = format!("{summary}\n{selected}\n{footer}");
+}
+
+fn cleanup(
+    mut commands: Commands,
+    world_query: Query<Entity, With<GameWorld>>,
+    ui_query: Query<Entity, With<EditorUi>>,
+) {
+    for entity in world_query.iter().chain(ui_query.iter()) {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,418 @@
+use bevy::{
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+};
+use bevy_flappy_macros::hex_to_color;
+
+use crate::input::{ActionState, GameAction};
+use crate::game::{
+    AppState,
+    course::{CourseSelection, CourseSettings, MAX_CODE_LEN, OverrideField},
+};
+use crate::locale::{Language, Phrase};
+use crate::settings::UserSettings;
+
+const MENU_BG_COLOR: Color = hex_to_color!("#e4ede6");
+const BUTTON_COLOR_IDLE: Color = hex_to_color!("#c3d8d2");
+const BUTTON_COLOR_HOVER: Color = hex_to_color!("#f4f5f4");
+const BUTTON_COLOR_PRESSED: Color = hex_to_color!("#c3d8d2");
+const INPUT_BG_COLOR: Color = hex_to_color!("#ffffff");
+const TEXT_COLOR: Color = hex_to_color!("#2f3e46");
+const ERROR_COLOR: Color = hex_to_color!("#c0392b");
+
+type QueryButton<'w, 's, 'a> = Query<
+    'w,
+    's,
+    (&'a Interaction, &'a EnterCodeOption, &'a mut BackgroundColor),
+    (Changed<Interaction>, With<Button>),
+>;
+
+#[derive(Resource, Default)]
+pub struct CodeInput {
+    pub text: String,
+    pub submitted: bool,
+}
+
+#[derive(Component)]
+pub struct EnterCodeMenu;
+
+#[derive(Component)]
+pub struct CodeInputText;
+
+#[derive(Component)]
+pub struct CodeErrorText;
+
+#[derive(Component)]
+pub struct OverrideText(pub OverrideField);
+
+#[derive(Component, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum EnterCodeOption {
+    Start,
+    Back,
+    Lower(OverrideField),
+    Raise(OverrideField),
+    // Back to the value of the code's difficulty preset
+    Reset(OverrideField),
+}
+
+pub struct EnterCodePlugin;
+
+impl Plugin for EnterCodePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CodeInput>()
+            .add_systems(OnEnter(AppState::EnterCode), setup)
+            .add_systems(
+                Update,
+                (
+                    handle_typing,
+                    handle_input,
+                    update_input_text,
+                    update_override_text,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::EnterCode)),
+            )
+            .add_systems(OnExit(AppState::EnterCode), cleanup);
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    mut code_input: ResMut<CodeInput>,
+    settings: Res<UserSettings>,
+) {
+    fn create_button(text: &str, option: EnterCodeOption) -> impl Bundle {
+        (
+            Button,
+            Node {
+                width: Val::Percent(40.0),
+                height: Val::Px(50.0),
+                margin: UiRect::all(Val::Px(10.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BorderRadius::all(Val::Px(10.0)),
+            BackgroundColor(BUTTON_COLOR_IDLE),
+            option,
+            children![(
+                Text(text.to_string()),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+            )],
+        )
+    }
+
+    fn small_button(text: &str, option: EnterCodeOption) -> impl Bundle {
+        (
+            Button,
+            Node {
+                width: Val::Px(28.0),
+                height: Val::Px(24.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BorderRadius::all(Val::Px(6.0)),
+            BackgroundColor(BUTTON_COLOR_IDLE),
+            option,
+            children![(
+                Text(text.to_string()),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+            )],
+        )
+    }
+
+    // Overrides edit the code in the box, so the code to share always matches
+    fn override_row(field: OverrideField, language: Language) -> impl Bundle {
+        (
+            Node {
+                width: Val::Percent(70.0),
+                height: Val::Px(28.0),
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            children![
+                (
+                    Text(language.translate(field.phrase()).to_string()),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(TEXT_COLOR),
+                ),
+                (
+                    Node {
+                        column_gap: Val::Px(6.0),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    children![
+                        small_button("-", EnterCodeOption::Lower(field)),
+                        (
+                            Button,
+                            Node {
+                                width: Val::Px(80.0),
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            BorderRadius::all(Val::Px(6.0)),
+                            BackgroundColor(BUTTON_COLOR_IDLE),
+                            EnterCodeOption::Reset(field),
+                            children![(
+                                OverrideText(field),
+                                Text::default(),
+                                TextFont {
+                                    font_size: 16.0,
+                                    ..default()
+                                },
+                            )],
+                        ),
+                        small_button("+", EnterCodeOption::Raise(field)),
+                    ],
+                ),
+            ],
+        )
+    }
+
+    let language = settings.language;
+    code_input.submitted = false;
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(MENU_BG_COLOR),
+        EnterCodeMenu,
+        children![
+            (
+                Text(language.translate(Phrase::EnterCourseCode).to_string()),
+                TextFont {
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(TEXT_COLOR),
+            ),
+            (
+                Node {
+                    width: Val::Percent(80.0),
+                    margin: UiRect::all(Val::Px(20.0)),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                BorderRadius::all(Val::Px(6.0)),
+                BackgroundColor(INPUT_BG_COLOR),
+                children![(
+                    CodeInputText,
+                    Text::default(),
+                    TextFont {
+                        font_size: 20.0,
+                        ..default()
+                    },
+                    TextColor(TEXT_COLOR),
+                )],
+            ),
+            (
+                CodeErrorText,
+                Text::default(),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(ERROR_COLOR),
+            ),
+            (
+                Node {
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(2.0),
+                    margin: UiRect::top(Val::Px(10.0)),
+                    ..default()
+                },
+                children![
+                    override_row(OverrideField::Gravity, language),
+                    override_row(OverrideField::JumpImpulse, language),
+                    override_row(OverrideField::PipeSpeed, language),
+                    override_row(OverrideField::MinGap, language),
+                    override_row(OverrideField::MaxGap, language),
+                    override_row(OverrideField::SpawnInterval, language),
+                ],
+            ),
+            (
+                Node {
+                    width: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    margin: UiRect::top(Val::Px(10.0)),
+                    ..default()
+                },
+                children![
+                    create_button(language.translate(Phrase::Back), EnterCodeOption::Back),
+                    create_button(language.translate(Phrase::Start), EnterCodeOption::Start),
+                ],
+            ),
+        ],
+    ));
+}
+
+fn handle_typing(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    actions: Res<ActionState>,
+    mut code_input: ResMut<CodeInput>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    if actions.just_pressed(GameAction::Confirm) {
+        code_input.submitted = true;
+    }
+    if actions.just_pressed(GameAction::Back) {
+        app_state.set(AppState::MainMenu);
+    }
+
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Character(chars) => {
+                for c in chars.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-') {
+                    if code_input.text.len() < MAX_CODE_LEN {
+                        code_input.text.push(c.to_ascii_uppercase());
+                    }
+                }
+            }
+            Key::Backspace => {
+                code_input.text.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn handle_input(
+    mut interaction_query: QueryButton,
+    mut code_input: ResMut<CodeInput>,
+    error_query: Single<&mut Text, With<CodeErrorText>>,
+    mut course_selection: ResMut<CourseSelection>,
+    settings: Res<UserSettings>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, option, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                match option {
+                    EnterCodeOption::Start => {
+                        code_input.submitted = true;
+                    }
+                    EnterCodeOption::Back => {
+                        app_state.set(AppState::MainMenu);
+                    }
+                    EnterCodeOption::Lower(field) => {
+                        edit_override(&mut code_input, &settings, *field, Some(-field.step()));
+                    }
+                    EnterCodeOption::Raise(field) => {
+                        edit_override(&mut code_input, &settings, *field, Some(field.step()));
+                    }
+                    EnterCodeOption::Reset(field) => {
+                        edit_override(&mut code_input, &settings, *field, None);
+                    }
+                }
+                *bg_color = BackgroundColor(BUTTON_COLOR_PRESSED);
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(BUTTON_COLOR_HOVER);
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(BUTTON_COLOR_IDLE);
+            }
+        }
+    }
+
+    if !code_input.submitted {
+        return;
+    }
+    code_input.submitted = false;
+
+    let mut error_text = error_query.into_inner();
+    match CourseSettings::from_code(&code_input.text) {
+        Ok(settings) => {
+            error_text.0.clear();
+            *course_selection = CourseSelection::Fixed(settings);
+            app_state.set(AppState::InGame);
+        }
+        Err(err) => {
+            error_text.0 = err.message(settings.language);
+        }
+    }
+}
+
+// Steps an override of the entered code, or clears it without a step. Without a valid
+// code, a new course is started on the chosen difficulty.
+fn edit_override(
+    code_input: &mut CodeInput,
+    settings: &UserSettings,
+    field: OverrideField,
+    step: Option<f32>,
+) {
+    let mut course = CourseSettings::from_code(&code_input.text)
+        .unwrap_or_else(|_| CourseSettings::random(settings.difficulty));
+    let value = step.map(|step| {
+        let current = course
+            .overrides
+            .get(field)
+            .unwrap_or_else(|| field.value(&course.config()));
+        let (min, max) = field.range();
+        (current + step).clamp(min, max)
+    });
+    course.overrides.set(field, value);
+    code_input.text = course.code();
+}
+
+fn update_override_text(
+    code_input: Res<CodeInput>,
+    settings: Res<UserSettings>,
+    mut text_query: Query<(&mut Text, Ref<OverrideText>)>,
+) {
+    let overrides = CourseSettings::from_code(&code_input.text)
+        .map(|course| course.overrides)
+        .unwrap_or_default();
+    for (mut text, marker) in &mut text_query {
+        if !code_input.is_changed() && !marker.is_added() {
+            continue;
+        }
+        text.0 = match overrides.get(marker.0) {
+            Some(value) if marker.0 == OverrideField::SpawnInterval => format!("{value:.2}s"),
+            Some(value) => format!("{value:.0}"),
+            None => settings.language.translate(Phrase::Default).to_string(),
+        };
+    }
+}
+
+fn update_input_text(
+    code_input: Res<CodeInput>,
+    input_query: Single<&mut Text, With<CodeInputText>>,
+) {
+    if code_input.is_changed() {
+        input_query.into_inner().0 = format!("{}_", code_input.text);
+    }
+}
+
+fn cleanup(mut commands: Commands, query: Query<Entity, With<EnterCodeMenu>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,174 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    components::{BirdTextures, Player},
+    constants::PlayPhase,
+};
+
+// Up, mid, down, mid like the original flap cycle
+const FRAME_ORDER: [usize; 4] = [0, 1, 2, 1];
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FlapTiming {
+    // Seconds per frame while gliding
+    pub frame_time: f32,
+    // Seconds per frame right after a flap
+    pub flap_frame_time: f32,
+    // Seconds per frame on the Get Ready screen
+    pub idle_frame_time: f32,
+    // How long the faster flap rate lasts after a flap
+    pub boost_duration: f32,
+}
+
+impl Default for FlapTiming {
+    fn default() -> Self {
+        Self {
+            frame_time: 0.1,
+            flap_frame_time: 0.04,
+            idle_frame_time: 0.18,
+            boost_duration: 0.25,
+        }
+    }
+}
+
+#[derive(Resource, Clone)]
+pub struct BirdAtlas {
+    pub image: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+    pub indices: [usize; 3],
+}
+
+#[derive(Component)]
+pub struct FlapAnimation {
+    pub timing: FlapTiming,
+    pub timer: Timer,
+    pub boost: Timer,
+    pub step: usize,
+}
+
+impl FlapAnimation {
+    pub fn new(timing: FlapTiming) -> Self {
+        let mut boost = Timer::from_seconds(timing.boost_duration, TimerMode::Once);
+        boost.tick(boost.duration());
+
+        Self {
+            timing,
+            timer: Timer::from_seconds(timing.idle_frame_time, TimerMode::Repeating),
+            boost,
+            step: 0,
+        }
+    }
+
+    pub fn boost(&mut self) {
+        self.boost.reset();
+    }
+
+    pub fn frame(&self) -> usize {
+        FRAME_ORDER[self.step]
+    }
+
+    fn frame_time(&self, phase: PlayPhase) -> f32 {
+        if phase == PlayPhase::GetReady {
+            self.timing.idle_frame_time
+        } else if !self.boost.finished() {
+            self.timing.flap_frame_time
+        } else {
+            self.timing.frame_time
+        }
+    }
+}
+
+impl BirdTextures {
+    pub fn frames(&self) -> [&Handle<Image>; 3] {
+        [&self.up, &self.mid, &self.down]
+    }
+}
+
+// Stitches the loaded flap frames into a single atlas for the current skin
+pub fn build_bird_atlas(
+    mut commands: Commands,
+    textures: Res<BirdTextures>,
+    mut images: ResMut<Assets<Image>>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    let frames = textures.frames();
+    let (Some(up), Some(mid), Some(down)) = (
+        images.get(frames[0]),
+        images.get(frames[1]),
+        images.get(frames[2]),
+    ) else {
+        return;
+    };
+
+    let mut builder = TextureAtlasBuilder::default();
+    builder
+        .add_texture(Some(frames[0].id()), up)
+        .add_texture(Some(frames[1].id()), mid)
+        .add_texture(Some(frames[2].id()), down);
+
+    let (layout, sources, image) = match builder.build() {
+        Ok(atlas) => atlas,
+        Err(err) => {
+            warn!("Failed to build bird atlas: {err}");
+            return;
+        }
+    };
+    let indices = frames.map(|frame| sources.texture_index(frame).unwrap_or_default());
+
+    commands.insert_resource(BirdAtlas {
+        image: images.add(image),
+        layout: layouts.add(layout),
+        indices,
+    });
+}
+
+pub fn animate_flap(
+    time: Res<Time>,
+    phase: Res<State<PlayPhase>>,
+    mut query: Query<&mut FlapAnimation, With<Player>>,
+) {
+    for mut animation in &mut query {
+        animation.boost.tick(time.delta());
+
+        let frame_time = animation.frame_time(*phase.get());
+        animation
+            .timer
+            .set_duration(std::time::Duration::from_secs_f32(frame_time));
+        animation.timer.tick(time.delta());
+
+        if animation.timer.just_finished() {
+            animation.step = (animation.step + 1) % FRAME_ORDER.len();
+        }
+    }
+}
+
+pub fn apply_flap_frame(
+    atlas: Option<Res<BirdAtlas>>,
+    textures: Res<BirdTextures>,
+    mut query: Query<(&FlapAnimation, &mut Sprite)>,
+) {
+    for (animation, mut sprite) in &mut query {
+        let frame = animation.frame();
+
+        match &atlas {
+            Some(atlas) => {
+                if sprite.image != atlas.image {
+                    sprite.image = atlas.image.clone();
+                }
+                sprite.texture_atlas = Some(TextureAtlas {
+                    layout: atlas.layout.clone(),
+                    index: atlas.indices[frame],
+                });
+            }
+            // Fall back to swapping images until the atlas is ready
+            None => {
+                let image = textures.frames()[frame];
+                if sprite.image != *image {
+                    sprite.image = image.clone();
+                }
+            }
+        }
+    }
+}
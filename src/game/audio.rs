@@ -1,41 +1,138 @@
-use crate::game::{AudioEvent, GameSounds};
-use bevy::prelude::*;
+use crate::game::{AudioEvent, GAME_DIMENSIONS, GameSounds};
+use crate::settings::UserSettings;
+use bevy::{audio::Volume, prelude::*};
 
-#[derive(Component)]
-pub struct BackgroundMusic;
+// Distance between the listener's ears. Sound effects are placed between them, close
+// enough that only the pan changes their loudness, not the distance.
+pub const EAR_GAP: f32 = 2.0;
+// Rodio plays a sound centred between the ears at three quarters volume in each
+const SPATIAL_GAIN: f32 = 4.0 / 3.0;
+
+// Which volume level an audio entity follows
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AudioChannel {
+    Music,
+    Sfx,
+}
+
+// Volume factor a sound effect was given when it started, on top of its channel level
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct SoundVolume(pub f32);
+
+// Pan from -1 (left) to 1 (right) of a sound at `x`, for a listener at `listener_x` who
+// hears `half_width` to each side. `width` scales how far sounds spread, 0 keeps them
+// all centred.
+pub fn stereo_pan(x: f32, listener_x: f32, half_width: f32, width: f32) -> f32 {
+    if half_width <= 0.0 {
+        return 0.0;
+    }
+    ((x - listener_x) / half_width).clamp(-1.0, 1.0) * width.clamp(0.0, 1.0)
+}
+
+// Where a sound with the given pan is placed relative to the listener, between its ears
+pub fn emitter_offset(pan: f32) -> Vec3 {
+    Vec3::X * pan * EAR_GAP / 2.0
+}
 
 pub fn play_audio_events(
     mut audio_events: EventReader<AudioEvent>,
     game_sounds: Res<GameSounds>,
+    settings: Res<UserSettings>,
+    listener_query: Query<&GlobalTransform, With<SpatialListener>>,
     mut commands: Commands,
 ) {
+    let level = settings.audio.channel(AudioChannel::Sfx) * SPATIAL_GAIN;
+    let listener = listener_query
+        .single()
+        .map_or(Vec3::ZERO, |transform| transform.translation());
     for event in audio_events.read() {
-        let audio_source = match event {
-            AudioEvent::Wing => game_sounds.wing.clone(),
-            AudioEvent::Point => game_sounds.point.clone(),
-            AudioEvent::Hit => game_sounds.hit.clone(),
-            AudioEvent::Die => game_sounds.die.clone(),
+        let Some((audio_source, volume, speed)) = game_sounds
+            .sounds
+            .get(&event.sound)
+            .and_then(|sound| sound.pick())
+        else {
+            continue;
         };
 
-        commands.spawn((AudioPlayer::new(audio_source), PlaybackSettings::ONCE));
+        let pan = stereo_pan(
+            event.position.x,
+            listener.x,
+            GAME_DIMENSIONS.0 / 2.0,
+            settings.audio.stereo_width,
+        );
+        commands.spawn((
+            AudioPlayer::new(audio_source),
+            PlaybackSettings::DESPAWN
+                .with_volume(Volume::Linear(level * volume))
+                .with_speed(speed)
+                .with_spatial(true),
+            Transform::from_translation(listener + emitter_offset(pan)),
+            AudioChannel::Sfx,
+            SoundVolume(volume),
+        ));
     }
 }
 
-// Background music system
-pub fn play_background_music(asset_server: Res<AssetServer>, mut commands: Commands) {
-    commands.spawn((
-        AudioPlayer::new(asset_server.load("audio/random_game_music.ogg")),
-        PlaybackSettings::LOOP,
-        BackgroundMusic,
-    ));
+// Sound effects already playing follow the levels as they are changed. Music fades
+// handle their own volume.
+pub fn apply_audio_levels(
+    settings: Res<UserSettings>,
+    mut sink_query: Query<(&AudioChannel, &SoundVolume, &mut SpatialAudioSink)>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for (channel, volume, mut sink) in &mut sink_query {
+        sink.set_volume(Volume::Linear(
+            settings.audio.channel(*channel) * SPATIAL_GAIN * volume.0,
+        ));
+    }
 }
 
-// Stop background music when entering game over
-pub fn stop_background_music(
-    mut commands: Commands,
-    background_music_query: Query<Entity, With<BackgroundMusic>>,
-) {
-    for entity in &background_music_query {
-        commands.entity(entity).despawn();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HALF_WIDTH: f32 = 288.0;
+
+    #[test]
+    fn centred_sound_is_not_panned() {
+        assert_eq!(stereo_pan(0.0, 0.0, HALF_WIDTH, 1.0), 0.0);
+        assert_eq!(stereo_pan(50.0, 50.0, HALF_WIDTH, 0.8), 0.0);
+    }
+
+    #[test]
+    fn screen_edges_pan_fully() {
+        assert_eq!(stereo_pan(-HALF_WIDTH, 0.0, HALF_WIDTH, 1.0), -1.0);
+        assert_eq!(stereo_pan(HALF_WIDTH, 0.0, HALF_WIDTH, 1.0), 1.0);
+        assert_eq!(stereo_pan(HALF_WIDTH / 2.0, 0.0, HALF_WIDTH, 1.0), 0.5);
+    }
+
+    #[test]
+    fn sounds_beyond_the_screen_are_clamped() {
+        assert_eq!(stereo_pan(-1000.0, 0.0, HALF_WIDTH, 1.0), -1.0);
+        assert_eq!(stereo_pan(1000.0, 0.0, HALF_WIDTH, 1.0), 1.0);
+        assert_eq!(stereo_pan(1000.0, 0.0, HALF_WIDTH, 0.8), 0.8);
+    }
+
+    #[test]
+    fn zero_width_keeps_sounds_centred() {
+        assert_eq!(stereo_pan(-HALF_WIDTH, 0.0, HALF_WIDTH, 0.0), 0.0);
+        assert_eq!(stereo_pan(HALF_WIDTH, 0.0, HALF_WIDTH, 0.0), 0.0);
+    }
+
+    #[test]
+    fn empty_hearing_range_keeps_sounds_centred() {
+        assert_eq!(stereo_pan(100.0, 0.0, 0.0, 1.0), 0.0);
+        assert_eq!(stereo_pan(100.0, 0.0, -10.0, 1.0), 0.0);
+    }
+
+    #[test]
+    fn emitter_sits_between_the_ears() {
+        assert_eq!(emitter_offset(0.0), Vec3::ZERO);
+        assert_eq!(emitter_offset(1.0), Vec3::X * EAR_GAP / 2.0);
+        assert_eq!(emitter_offset(-1.0), Vec3::X * -EAR_GAP / 2.0);
+        assert_eq!(emitter_offset(0.5), Vec3::X * EAR_GAP / 4.0);
     }
 }
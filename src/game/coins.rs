@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::game::{
+    collision::check_collision,
+    components::*,
+    config::GameConfig,
+    constants::*,
+    course::Course,
+    events::{AudioEvent, Sound},
+    level::level_active,
+    powerups::{Collectible, Shrink, bird_collision_size, spawn_power_ups},
+};
+use crate::profile::Profile;
+
+pub const COIN_SPRITE_PATH: &str = "sprites/coin.png";
+
+const COIN_CHANCE: f64 = 0.6;
+const COINS_PER_ROW: usize = 3;
+// Coins lead into the gap from in front of the pipes
+const COIN_ROW_START: f32 = -60.0;
+const COIN_SPACING: f32 = 30.0;
+const COIN_SIZE: Vec2 = Vec2::splat(16.0);
+
+#[derive(Component, Default)]
+#[require(Collectible)]
+pub struct Coin;
+
+// Coins picked up during the current run, added to the wallet when it ends
+#[derive(Resource, Default)]
+pub struct RunCoins(pub u32);
+
+#[derive(Component)]
+pub struct CoinHud;
+
+#[derive(Component)]
+pub struct CoinText;
+
+pub struct CoinPlugin;
+
+impl Plugin for CoinPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunCoins>()
+            .add_systems(OnEnter(AppState::InGame), setup_coins)
+            .add_systems(
+                Update,
+                (
+                    // After power-ups so both draw from the pickup stream in a fixed order
+                    spawn_coins.after(spawn_power_ups).run_if(not(level_active)),
+                    collect_coins,
+                )
+                    .run_if(in_state(PlayPhase::Playing)),
+            )
+            .add_systems(OnExit(AppState::InGame), cleanup_coin_hud)
+            .add_systems(OnEnter(AppState::GameOver), bank_coins);
+    }
+}
+
+fn setup_coins(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut run_coins: ResMut<RunCoins>,
+) {
+    run_coins.0 = 0;
+
+    commands.spawn((
+        CoinHud,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(20.0),
+            right: Val::Px(20.0),
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(6.0),
+            ..default()
+        },
+        children![
+            (
+                Node {
+                    width: Val::Px(20.0),
+                    height: Val::Px(20.0),
+                    ..default()
+                },
+                ImageNode::new(asset_server.load(COIN_SPRITE_PATH)),
+            ),
+            (
+                CoinText,
+                Text("0".to_string()),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextShadow::default(),
+            ),
+        ],
+    ));
+}
+
+fn cleanup_coin_hud(mut commands: Commands, query: Query<Entity, With<CoinHud>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+// Lays a row of coins along the flight path into some newly generated pipes
+fn spawn_coins(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut course: ResMut<Course>,
+    pipe_pair_query: Query<Entity, Added<PipePair>>,
+) {
+    for pipe_pair in &pipe_pair_query {
+        if !course.pickup_rng.random_bool(COIN_CHANCE) {
+            continue;
+        }
+
+        commands.entity(pipe_pair).with_children(|parent| {
+            for i in 0..COINS_PER_ROW {
+                parent.spawn((
+                    Coin,
+                    Sprite {
+                        image: asset_server.load(COIN_SPRITE_PATH),
+                        custom_size: Some(COIN_SIZE),
+                        ..default()
+                    },
+                    Transform::from_xyz(COIN_ROW_START - i as f32 * COIN_SPACING, 0., 1.),
+                ));
+            }
+        });
+    }
+}
+
+fn collect_coins(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    mut run_coins: ResMut<RunCoins>,
+    bird_query: ActiveBirds<(&GlobalTransform, Option<&Shrink>)>,
+    coin_query: Query<(Entity, &GlobalTransform), With<Coin>>,
+    coin_text_query: Single<&mut Text, With<CoinText>>,
+    mut audio_events: EventWriter<AudioEvent>,
+) {
+    let mut collected = 0;
+    let mut collected_at = Vec2::ZERO;
+    for (coin, coin_transform) in &coin_query {
+        let touched = bird_query.iter().any(|(bird_transform, shrink)| {
+            check_collision(
+                bird_transform,
+                bird_collision_size(&config, shrink),
+                coin_transform,
+                COIN_SIZE,
+            )
+        });
+
+        if touched {
+            commands.entity(coin).despawn();
+            collected += 1;
+            collected_at = coin_transform.translation().truncate();
+        }
+    }
+
+    if collected > 0 {
+        run_coins.0 += collected;
+        coin_text_query.into_inner().0 = run_coins.0.to_string();
+        audio_events.write(AudioEvent::new(Sound::Coin, collected_at));
+    }
+}
+
+fn bank_coins(run_coins: Res<RunCoins>, mut profile: ResMut<Profile>) {
+    if run_coins.0 > 0 {
+        profile.coins += run_coins.0;
+    }
+}
@@ -1,21 +1,22 @@
 use bevy::prelude::*;
-use crate::game::constants::*;
 
-pub fn check_collision(player_transform: &GlobalTransform, pipe_transform: &GlobalTransform) -> bool {
+pub fn check_collision(
+    a_transform: &GlobalTransform,
+    a_size: Vec2,
+    b_transform: &GlobalTransform,
+    b_size: Vec2,
+) -> bool {
     // Simple AABB collision detection using world coordinates
-    let player_left = player_transform.translation().x - PLAYER_COLLISION_WIDTH / 2.0;
-    let player_right = player_transform.translation().x + PLAYER_COLLISION_WIDTH / 2.0;
-    let player_top = player_transform.translation().y + PLAYER_COLLISION_HEIGHT / 2.0;
-    let player_bottom = player_transform.translation().y - PLAYER_COLLISION_HEIGHT / 2.0;
+    let a_left = a_transform.translation().x - a_size.x / 2.0;
+    let a_right = a_transform.translation().x + a_size.x / 2.0;
+    let a_top = a_transform.translation().y + a_size.y / 2.0;
+    let a_bottom = a_transform.translation().y - a_size.y / 2.0;
 
-    let pipe_left = pipe_transform.translation().x - PIPE_COLLISION_WIDTH / 2.0;
-    let pipe_right = pipe_transform.translation().x + PIPE_COLLISION_WIDTH / 2.0;
-    let pipe_top = pipe_transform.translation().y + PIPE_COLLISION_HEIGHT / 2.0;
-    let pipe_bottom = pipe_transform.translation().y - PIPE_COLLISION_HEIGHT / 2.0;
+    let b_left = b_transform.translation().x - b_size.x / 2.0;
+    let b_right = b_transform.translation().x + b_size.x / 2.0;
+    let b_top = b_transform.translation().y + b_size.y / 2.0;
+    let b_bottom = b_transform.translation().y - b_size.y / 2.0;
 
     // Check if rectangles overlap
-    player_left < pipe_right
-        && player_right > pipe_left
-        && player_top > pipe_bottom
-        && player_bottom < pipe_top
-} 
\ No newline at end of file
+    a_left < b_right && a_right > b_left && a_top > b_bottom && a_bottom < b_top
+}
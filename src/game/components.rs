@@ -1,5 +1,9 @@
+use std::collections::HashMap;
+
 use bevy::prelude::*;
 
+use crate::game::{events::Sound, sound_packs::LoadedSound};
+
 #[derive(Component)]
 pub struct GameWorld;
 
@@ -9,6 +13,12 @@ pub struct GameUi;
 #[derive(Component)]
 pub struct GameOverLayer;
 
+#[derive(Component)]
+pub struct GetReadyLayer;
+
+#[derive(Component)]
+pub struct PauseLayer;
+
 #[derive(Component)]
 pub struct BackgroundImage;
 
@@ -19,6 +29,31 @@ pub struct PlatformImage;
 #[require(Sprite, Transform)]
 pub struct Player;
 
+// Which local player a bird belongs to, also used to pick its flap action and colour
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PlayerSlot(pub usize);
+
+#[derive(Component, Clone, Default)]
+pub struct PlayerScore(pub u32);
+
+// Bird that hit a pipe or the ground and is falling out of the round
+#[derive(Component)]
+pub struct Eliminated {
+    pub at: f32,
+}
+
+// Birds that are still in the round
+pub type ActiveBirds<'w, 's, D, F = ()> = Query<'w, 's, D, (With<Player>, Without<Eliminated>, F)>;
+
+#[derive(Resource, Clone, Copy)]
+pub struct PlayerCount(pub usize);
+
+impl Default for PlayerCount {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
 #[derive(Component, Deref, DerefMut)]
 pub struct Velocity(pub f32);
 
@@ -35,23 +70,22 @@ pub struct BirdTextures {
 #[derive(Resource, Clone, Default)]
 pub struct Score(pub u32);
 
+// Pipes passed by the leading bird. Difficulty and medals follow it rather than the
+// score, which precision scoring inflates.
+#[derive(Resource, Clone, Default)]
+pub struct PipesPassed(pub u32);
+
 #[derive(Component, Clone)]
-#[require(Text)]
 pub struct ScoreText;
 
 #[derive(Component, Clone)]
 #[require(Sprite, Transform, Collider)]
 pub struct Pipe;
 
-#[derive(Component, Clone)]
+#[derive(Component, Clone, Default)]
 pub struct PipePair {
     pub scored: bool,
-}
-
-impl Default for PipePair {
-    fn default() -> Self {
-        Self { scored: false }
-    }
+    pub gap_size: f32,
 }
 
 #[derive(Resource, Clone)]
@@ -60,6 +94,15 @@ pub struct PipeTextures {
     pub red_pipe: Handle<Image>,
 }
 
+impl PipeTextures {
+    pub fn load(asset_server: &AssetServer) -> Self {
+        Self {
+            green_pipe: asset_server.load("sprites/pipe-green.png"),
+            red_pipe: asset_server.load("sprites/pipe-red.png"),
+        }
+    }
+}
+
 #[derive(Resource)]
 pub struct PipeInterval(pub Timer);
 
@@ -85,12 +128,10 @@ impl PipeInterval {
     }
 }
 
+// Sounds of the chosen sound pack
 #[derive(Resource)]
 pub struct GameSounds {
-    pub wing: Handle<AudioSource>,
-    pub point: Handle<AudioSource>,
-    pub hit: Handle<AudioSource>,
-    pub die: Handle<AudioSource>,
+    pub sounds: HashMap<Sound, LoadedSound>,
 }
 
 #[derive(Resource)]
@@ -7,6 +7,7 @@ pub struct GameConfig {
     pub pipes: PipeConfig,
     pub audio: AudioConfig,
     pub ui: UiConfig,
+    pub medals: MedalConfig,
 }
 
 #[derive(Clone)]
@@ -37,6 +38,7 @@ pub struct AudioConfig {
     pub point_sound: String,
     pub hit_sound: String,
     pub die_sound: String,
+    pub swoosh_sound: String,
 }
 
 #[derive(Clone)]
@@ -46,6 +48,15 @@ pub struct UiConfig {
     pub button_colors: ButtonColors,
 }
 
+// Lowest score that earns each medal
+#[derive(Clone)]
+pub struct MedalConfig {
+    pub bronze: u32,
+    pub silver: u32,
+    pub gold: u32,
+    pub platinum: u32,
+}
+
 #[derive(Clone)]
 pub struct ButtonColors {
     pub idle: Color,
@@ -79,6 +90,7 @@ impl Default for GameConfig {
                 point_sound: "audio/point.ogg".to_string(),
                 hit_sound: "audio/hit.ogg".to_string(),
                 die_sound: "audio/die.ogg".to_string(),
+                swoosh_sound: "audio/swoosh.ogg".to_string(),
             },
             ui: UiConfig {
                 score_font_size: 50.0,
@@ -89,6 +101,12 @@ impl Default for GameConfig {
                     pressed: BUTTON_COLOR_PRESSED,
                 },
             },
+            medals: MedalConfig {
+                bronze: BRONZE_MEDAL_SCORE,
+                silver: SILVER_MEDAL_SCORE,
+                gold: GOLD_MEDAL_SCORE,
+                platinum: PLATINUM_MEDAL_SCORE,
+            },
         }
     }
 } 
\ No newline at end of file
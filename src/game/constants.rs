@@ -8,6 +8,23 @@ pub enum AppState {
     InGame,
     GameOver,
     Settings,
+    Editor,
+    EnterCode,
+    Controls,
+    Shop,
+    Leaderboard,
+    Stats,
+    Achievements,
+    Profiles,
+}
+
+#[derive(SubStates, Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+#[source(AppState = AppState::InGame)]
+pub enum PlayPhase {
+    #[default]
+    GetReady,
+    Playing,
+    Paused,
 }
 
 pub const GAME_DIMENSIONS: (f32, f32) = (BG_IMG_DIMENSIONS.0 * 2.0, BG_IMG_DIMENSIONS.1);
@@ -15,21 +32,42 @@ pub const GAME_DIMENSIONS: (f32, f32) = (BG_IMG_DIMENSIONS.0 * 2.0, BG_IMG_DIMEN
 pub const BUTTON_COLOR_IDLE: Color = hex_to_color!("#E5E5E5");
 pub const BUTTON_COLOR_HOVER: Color = hex_to_color!("#D3D3D3");
 pub const BUTTON_COLOR_PRESSED: Color = hex_to_color!("#A9A9A9");
+pub const NEW_BEST_COLOR: Color = hex_to_color!("#FFD54F");
 
 pub const BG_IMG_DIMENSIONS: (f32, f32) = (288.0, 512.0);
 pub const BG_SPRITE_PATH: &str = "sprites/background-day.png";
 pub const PLATFORM_SPRITE_PATH: &str = "sprites/base.png";
+pub const GET_READY_SPRITE_PATH: &str = "sprites/message.png";
 
 pub const Z_POS_BG: f32 = -10.0;
 pub const Z_POS_PLATFORM: f32 = -3.0;
 pub const Z_POS_PIPE: f32 = -4.0;
 pub const Z_POS_PLAYER: f32 = 10.0;
 
+pub const MAX_PLAYERS: usize = 4;
+pub const PLAYER_COLORS: [Color; MAX_PLAYERS] = [
+    Color::WHITE,
+    hex_to_color!("#ff9f9f"),
+    hex_to_color!("#9fd4ff"),
+    hex_to_color!("#a8f0a0"),
+];
+// Vertical gap between birds at the start of a round
+pub const PLAYER_SPAWN_SPACING: f32 = 30.0;
+
+// Scores needed for each medal, as in the original game
+pub const BRONZE_MEDAL_SCORE: u32 = 10;
+pub const SILVER_MEDAL_SCORE: u32 = 20;
+pub const GOLD_MEDAL_SCORE: u32 = 30;
+pub const PLATINUM_MEDAL_SCORE: u32 = 40;
+
 pub const MAX_PLAYER_ROTATION: f32 = 25.0;
+pub const GET_READY_BOB_HEIGHT: f32 = 8.0;
+pub const GET_READY_BOB_SPEED: f32 = 4.0;
 
-pub const BG_SPEED: f32 = 0.2;
-pub const PLATFORM_SPEED: f32 = 1.0;
-pub const PIPE_SPEED: f32 = 1.0;
+// Scroll speeds in pixels per second, so the course keeps its pace at any frame rate
+pub const BG_SPEED: f32 = 12.0;
+pub const PLATFORM_SPEED: f32 = 60.0;
+pub const PIPE_SPEED: f32 = 60.0;
 
 pub const GRAVITY: f32 = -700.0;
 pub const JUMP_IMPULSE: f32 = 300.0;
@@ -41,6 +79,8 @@ pub const PIPE_HEIGHT: f32 = 320.0;
 pub const PIPE_LEGROOM: f32 = 100.0;
 pub const MAX_PIPE_GAP: f32 = 250.0;
 pub const MIN_PIPE_GAP: f32 = 150.0;
+pub const PIPE_SPAWN_X: f32 = BG_IMG_DIMENSIONS.0 + PIPE_WIDTH / 2.0;
+pub const PIPE_DESPAWN_X: f32 = -GAME_DIMENSIONS.0 / 2.0 - PIPE_WIDTH / 2.0;
 
 pub const INITIAL_PIPE_INTERVAL: f32 = 3.0;
 
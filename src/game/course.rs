@@ -0,0 +1,536 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::game::config::GameConfig;
+use crate::locale::{Language, Phrase};
+
+const CODE_VERSION: u8 = 1;
+const CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const CODE_GROUP_LEN: usize = 4;
+// Version and preset, seed, override mask and checksum
+const CODE_HEADER_BYTES: usize = 7;
+// A code with every override set, two bytes each
+const MAX_CODE_BYTES: usize = CODE_HEADER_BYTES + 2 * OverrideField::ALL.len();
+const MAX_CODE_SYMBOLS: usize = (MAX_CODE_BYTES * 8).div_ceil(5);
+// Length of the longest code as shown, dashes included
+pub const MAX_CODE_LEN: usize = MAX_CODE_SYMBOLS + (MAX_CODE_SYMBOLS - 1) / CODE_GROUP_LEN;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DifficultyPreset {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl DifficultyPreset {
+    pub const ALL: [DifficultyPreset; 3] = [Self::Easy, Self::Normal, Self::Hard];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Easy => "Easy",
+            Self::Normal => "Normal",
+            Self::Hard => "Hard",
+        }
+    }
+
+    pub fn phrase(&self) -> Phrase {
+        match self {
+            Self::Easy => Phrase::Easy,
+            Self::Normal => Phrase::Normal,
+            Self::Hard => Phrase::Hard,
+        }
+    }
+
+    pub fn apply(&self, config: &mut GameConfig) {
+        let (gap, speed, interval, gravity) = match self {
+            Self::Easy => (1.15, 0.85, 1.15, 0.9),
+            Self::Normal => (1.0, 1.0, 1.0, 1.0),
+            Self::Hard => (0.85, 1.2, 0.9, 1.1),
+        };
+
+        config.pipes.min_gap *= gap;
+        config.pipes.max_gap *= gap;
+        config.pipes.speed *= speed;
+        config.pipes.spawn_interval *= interval;
+        config.player.gravity *= gravity;
+    }
+
+    fn to_bits(self) -> u8 {
+        match self {
+            Self::Easy => 0,
+            Self::Normal => 1,
+            Self::Hard => 2,
+        }
+    }
+
+    fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            0 => Some(Self::Easy),
+            1 => Some(Self::Normal),
+            2 => Some(Self::Hard),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ConfigOverrides {
+    pub gravity: Option<f32>,
+    pub jump_impulse: Option<f32>,
+    pub pipe_speed: Option<f32>,
+    pub min_gap: Option<f32>,
+    pub max_gap: Option<f32>,
+    pub spawn_interval: Option<f32>,
+}
+
+// One setting a course code can override, as listed on the Enter code screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverrideField {
+    Gravity,
+    JumpImpulse,
+    PipeSpeed,
+    MinGap,
+    MaxGap,
+    SpawnInterval,
+}
+
+impl OverrideField {
+    pub const ALL: [OverrideField; 6] = [
+        Self::Gravity,
+        Self::JumpImpulse,
+        Self::PipeSpeed,
+        Self::MinGap,
+        Self::MaxGap,
+        Self::SpawnInterval,
+    ];
+
+    pub fn phrase(&self) -> Phrase {
+        match self {
+            Self::Gravity => Phrase::Gravity,
+            Self::JumpImpulse => Phrase::Jump,
+            Self::PipeSpeed => Phrase::PipeSpeed,
+            Self::MinGap => Phrase::MinGap,
+            Self::MaxGap => Phrase::MaxGap,
+            Self::SpawnInterval => Phrase::PipeInterval,
+        }
+    }
+
+    // Change per press and the range kept to, which all fit the packed code
+    pub fn step(&self) -> f32 {
+        match self {
+            Self::Gravity => 50.0,
+            Self::JumpImpulse => 25.0,
+            Self::PipeSpeed => 5.0,
+            Self::MinGap | Self::MaxGap => 10.0,
+            Self::SpawnInterval => 0.25,
+        }
+    }
+
+    pub fn range(&self) -> (f32, f32) {
+        match self {
+            Self::Gravity => (-2000.0, -200.0),
+            Self::JumpImpulse => (100.0, 800.0),
+            Self::PipeSpeed => (20.0, 200.0),
+            Self::MinGap | Self::MaxGap => (80.0, 300.0),
+            Self::SpawnInterval => (0.5, 6.0),
+        }
+    }
+
+    // Value the course plays with when the field isn't overridden
+    pub fn value(&self, config: &GameConfig) -> f32 {
+        match self {
+            Self::Gravity => config.player.gravity,
+            Self::JumpImpulse => config.player.jump_impulse,
+            Self::PipeSpeed => config.pipes.speed,
+            Self::MinGap => config.pipes.min_gap,
+            Self::MaxGap => config.pipes.max_gap,
+            Self::SpawnInterval => config.pipes.spawn_interval,
+        }
+    }
+}
+
+impl ConfigOverrides {
+    // Fixed-point scale of each field when packed into a course code
+    const SCALES: [f32; 6] = [1.0, 1.0, 100.0, 1.0, 1.0, 100.0];
+
+    pub fn get(&self, field: OverrideField) -> Option<f32> {
+        self.fields()[field as usize]
+    }
+
+    pub fn set(&mut self, field: OverrideField, value: Option<f32>) {
+        let mut fields = self.fields();
+        fields[field as usize] = value;
+        *self = Self::from_fields(fields);
+    }
+
+    fn fields(&self) -> [Option<f32>; 6] {
+        [
+            self.gravity,
+            self.jump_impulse,
+            self.pipe_speed,
+            self.min_gap,
+            self.max_gap,
+            self.spawn_interval,
+        ]
+    }
+
+    fn from_fields(fields: [Option<f32>; 6]) -> Self {
+        Self {
+            gravity: fields[0],
+            jump_impulse: fields[1],
+            pipe_speed: fields[2],
+            min_gap: fields[3],
+            max_gap: fields[4],
+            spawn_interval: fields[5],
+        }
+    }
+
+    pub fn apply(&self, config: &mut GameConfig) {
+        if let Some(gravity) = self.gravity {
+            config.player.gravity = gravity;
+        }
+        if let Some(jump_impulse) = self.jump_impulse {
+            config.player.jump_impulse = jump_impulse;
+        }
+        if let Some(pipe_speed) = self.pipe_speed {
+            config.pipes.speed = pipe_speed;
+        }
+        if let Some(min_gap) = self.min_gap {
+            config.pipes.min_gap = min_gap;
+        }
+        if let Some(max_gap) = self.max_gap {
+            config.pipes.max_gap = max_gap;
+        }
+        if let Some(spawn_interval) = self.spawn_interval {
+            config.pipes.spawn_interval = spawn_interval.max(0.1);
+        }
+        config.pipes.max_gap = config.pipes.max_gap.max(config.pipes.min_gap);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CourseSettings {
+    pub seed: u32,
+    pub preset: DifficultyPreset,
+    pub overrides: ConfigOverrides,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CourseCodeError {
+    InvalidCharacter(char),
+    TooShort,
+    TooLong,
+    ChecksumMismatch,
+    UnsupportedVersion(u8),
+    InvalidPreset(u8),
+}
+
+impl CourseCodeError {
+    pub fn message(&self, language: Language) -> String {
+        match self {
+            Self::InvalidCharacter(c) => language.fill(Phrase::InvalidCodeCharacter, &[c]),
+            Self::TooShort => language.translate(Phrase::CodeTooShort).to_string(),
+            Self::TooLong => language.translate(Phrase::CodeTooLong).to_string(),
+            Self::ChecksumMismatch => language.translate(Phrase::CodeTypo).to_string(),
+            Self::UnsupportedVersion(version) => {
+                language.fill(Phrase::UnsupportedCodeVersion, &[version])
+            }
+            Self::InvalidPreset(preset) => language.fill(Phrase::UnknownPreset, &[preset]),
+        }
+    }
+}
+
+impl fmt::Display for CourseCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message(Language::English))
+    }
+}
+
+impl std::error::Error for CourseCodeError {}
+
+impl CourseSettings {
+    pub fn random(preset: DifficultyPreset) -> Self {
+        Self {
+            seed: rand::random(),
+            preset,
+            overrides: ConfigOverrides::default(),
+        }
+    }
+
+    pub fn config(&self) -> GameConfig {
+        let mut config = GameConfig::default();
+        self.preset.apply(&mut config);
+        self.overrides.apply(&mut config);
+        config
+    }
+
+    pub fn code(&self) -> String {
+        let mut bytes = vec![CODE_VERSION << 4 | self.preset.to_bits()];
+        bytes.extend_from_slice(&self.seed.to_be_bytes());
+
+        let fields = self.overrides.fields();
+        let mask = fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.is_some())
+            .fold(0u8, |mask, (i, _)| mask | 1 << i);
+        bytes.push(mask);
+
+        for (field, scale) in fields.iter().zip(ConfigOverrides::SCALES) {
+            if let Some(value) = field {
+                let packed = (value * scale).round() as i16;
+                bytes.extend_from_slice(&packed.to_be_bytes());
+            }
+        }
+        bytes.push(checksum(&bytes));
+
+        let encoded = encode_base32(&bytes);
+        encoded
+            .as_bytes()
+            .chunks(CODE_GROUP_LEN)
+            .map(|group| std::str::from_utf8(group).unwrap())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    pub fn from_code(code: &str) -> Result<Self, CourseCodeError> {
+        let bytes = decode_base32(code)?;
+        let (checksum_byte, body) = bytes.split_last().ok_or(CourseCodeError::TooShort)?;
+        if body.len() < 6 {
+            return Err(CourseCodeError::TooShort);
+        }
+        if checksum(body) != *checksum_byte {
+            return Err(CourseCodeError::ChecksumMismatch);
+        }
+
+        let version = body[0] >> 4;
+        if version != CODE_VERSION {
+            return Err(CourseCodeError::UnsupportedVersion(version));
+        }
+        let preset = DifficultyPreset::from_bits(body[0] & 0x0f)
+            .ok_or(CourseCodeError::InvalidPreset(body[0] & 0x0f))?;
+        let seed = u32::from_be_bytes([body[1], body[2], body[3], body[4]]);
+        let mask = body[5];
+
+        let mut fields = [None; 6];
+        let mut rest = &body[6..];
+        for (i, scale) in ConfigOverrides::SCALES.iter().enumerate() {
+            if mask & (1 << i) == 0 {
+                continue;
+            }
+            let [hi, lo, tail @ ..] = rest else {
+                return Err(CourseCodeError::TooShort);
+            };
+            fields[i] = Some(i16::from_be_bytes([*hi, *lo]) as f32 / scale);
+            rest = tail;
+        }
+        if !rest.is_empty() {
+            return Err(CourseCodeError::TooLong);
+        }
+
+        Ok(Self {
+            seed,
+            preset,
+            overrides: ConfigOverrides::from_fields(fields),
+        })
+    }
+}
+
+// How the next run picks its course
+#[derive(Resource, Debug, Clone, Default)]
+pub enum CourseSelection {
+    #[default]
+    Random,
+    Fixed(CourseSettings),
+}
+
+#[derive(Resource)]
+pub struct Course {
+    pub settings: CourseSettings,
+    pub rng: ChaCha8Rng,
+    // Separate stream for pickups, so adding them doesn't change the pipes of a course
+    pub pickup_rng: ChaCha8Rng,
+}
+
+impl Course {
+    pub fn new(settings: CourseSettings) -> Self {
+        let mut pickup_rng = ChaCha8Rng::seed_from_u64(settings.seed as u64);
+        pickup_rng.set_stream(1);
+
+        Self {
+            rng: ChaCha8Rng::seed_from_u64(settings.seed as u64),
+            pickup_rng,
+            settings,
+        }
+    }
+}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes
+        .iter()
+        .fold(0x5a, |acc: u8, byte| acc.rotate_left(3) ^ byte)
+}
+
+fn encode_base32(bytes: &[u8]) -> String {
+    let mut output = String::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for byte in bytes {
+        buffer = buffer << 8 | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            output.push(CODE_ALPHABET[(buffer >> bits & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        output.push(CODE_ALPHABET[(buffer << (5 - bits) & 0x1f) as usize] as char);
+    }
+    output
+}
+
+fn decode_base32(code: &str) -> Result<Vec<u8>, CourseCodeError> {
+    let mut output = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for c in code.chars().filter(|c| !matches!(c, '-' | ' ')) {
+        // Accept the usual look-alike characters people type by mistake
+        let normalized = match c.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            other => other,
+        };
+        let value = CODE_ALPHABET
+            .iter()
+            .position(|&symbol| symbol as char == normalized)
+            .ok_or(CourseCodeError::InvalidCharacter(c))?;
+
+        buffer = buffer << 5 | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits & 0xff) as u8);
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn every_override() -> ConfigOverrides {
+        ConfigOverrides {
+            gravity: Some(-850.0),
+            jump_impulse: Some(325.0),
+            pipe_speed: Some(72.5),
+            min_gap: Some(120.0),
+            max_gap: Some(220.0),
+            spawn_interval: Some(2.25),
+        }
+    }
+
+    #[test]
+    fn code_round_trips() {
+        let overrides = [
+            ConfigOverrides::default(),
+            ConfigOverrides {
+                pipe_speed: Some(90.0),
+                ..default()
+            },
+            ConfigOverrides {
+                gravity: Some(-1200.0),
+                max_gap: Some(180.0),
+                spawn_interval: Some(1.5),
+                ..default()
+            },
+            every_override(),
+        ];
+        for preset in DifficultyPreset::ALL {
+            for seed in [0, 1, 0x1234_5678, u32::MAX] {
+                for overrides in overrides {
+                    let settings = CourseSettings {
+                        seed,
+                        preset,
+                        overrides,
+                    };
+                    let code = settings.code();
+                    assert_eq!(CourseSettings::from_code(&code), Ok(settings));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn overrides_reach_the_config() {
+        let settings = CourseSettings {
+            seed: 7,
+            preset: DifficultyPreset::Hard,
+            overrides: every_override(),
+        };
+        let config = CourseSettings::from_code(&settings.code())
+            .unwrap()
+            .config();
+        for field in OverrideField::ALL {
+            assert_eq!(Some(field.value(&config)), every_override().get(field));
+        }
+    }
+
+    #[test]
+    fn code_is_typed_loosely() {
+        let settings = CourseSettings {
+            seed: 0xdead_beef,
+            preset: DifficultyPreset::Hard,
+            overrides: every_override(),
+        };
+        // Spaces for dashes, lower case and O for zero all still read back
+        let code = settings.code().replace('-', " ").replace('0', "O");
+        let code = code.to_lowercase();
+        assert_eq!(CourseSettings::from_code(&code), Ok(settings));
+    }
+
+    #[test]
+    fn longest_code_fits_the_input() {
+        let settings = CourseSettings {
+            seed: u32::MAX,
+            preset: DifficultyPreset::Hard,
+            overrides: every_override(),
+        };
+        assert_eq!(settings.code().len(), MAX_CODE_LEN);
+    }
+
+    #[test]
+    fn override_ranges_fit_the_code() {
+        for field in OverrideField::ALL {
+            let (min, max) = field.range();
+            for value in [min, max] {
+                let mut settings = CourseSettings::random(DifficultyPreset::Normal);
+                settings.overrides.set(field, Some(value));
+                let decoded = CourseSettings::from_code(&settings.code()).unwrap();
+                assert_eq!(decoded.overrides.get(field), Some(value));
+            }
+        }
+    }
+
+    #[test]
+    fn typos_are_caught() {
+        let mut code = CourseSettings::random(DifficultyPreset::Normal).code();
+        let first = code.remove(0);
+        code.insert(0, if first == 'Z' { 'Y' } else { 'Z' });
+        assert_eq!(
+            CourseSettings::from_code(&code),
+            Err(CourseCodeError::ChecksumMismatch)
+        );
+        assert_eq!(
+            CourseSettings::from_code("ABCD"),
+            Err(CourseCodeError::TooShort)
+        );
+    }
+}
@@ -1,9 +1,74 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Event)]
-pub enum AudioEvent {
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Sound {
     Wing,
     Point,
     Hit,
     Die,
-} 
\ No newline at end of file
+    PowerUp,
+    Coin,
+}
+
+impl Sound {
+    pub const ALL: [Sound; 6] = [
+        Self::Wing,
+        Self::Point,
+        Self::Hit,
+        Self::Die,
+        Self::PowerUp,
+        Self::Coin,
+    ];
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeathCause {
+    TopPipe,
+    BottomPipe,
+    Ground,
+}
+
+impl DeathCause {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::TopPipe => "Top pipe",
+            Self::BottomPipe => "Bottom pipe",
+            Self::Ground => "Ground",
+        }
+    }
+}
+
+// A sound effect and the place in the game world it comes from
+#[derive(Event, Clone, Copy, Debug)]
+pub struct AudioEvent {
+    pub sound: Sound,
+    pub position: Vec2,
+}
+
+impl AudioEvent {
+    pub fn new(sound: Sound, position: Vec2) -> Self {
+        Self { sound, position }
+    }
+}
+
+// What happened to each bird, for lifetime statistics
+#[derive(Event, Clone, Copy, Debug)]
+pub enum GameplayEvent {
+    Flap {
+        slot: usize,
+    },
+    PipePassed {
+        slot: usize,
+    },
+    // Passed a pipe close to its edge
+    NearMiss {
+        slot: usize,
+    },
+    Death {
+        slot: usize,
+        cause: DeathCause,
+        // Where the bird was in the game world
+        position: Vec2,
+    },
+}
@@ -0,0 +1,315 @@
+use std::{fs, io, path::PathBuf};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    components::*,
+    constants::*,
+    course::{CourseSelection, CourseSettings},
+    level::{ActiveLevel, CourseProgress},
+};
+use crate::locale::{Language, Phrase};
+use crate::profile::ActiveProfile;
+use crate::settings::UserSettings;
+use crate::storage;
+
+pub const REPLAYS_DIR: &str = "replays";
+pub const BEST_REPLAY: &str = "best";
+pub const LAST_REPLAY: &str = "last";
+
+// Course distance between two recorded bird positions
+const SAMPLE_STEP: f32 = 4.0;
+const GHOST_ALPHA: f32 = 0.4;
+const GHOST_AHEAD_COLOR: Color = Color::srgb(0.55, 0.95, 0.55);
+const GHOST_BEHIND_COLOR: Color = Color::srgb(1.0, 0.55, 0.55);
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Replay {
+    // Code of the course the run was played on
+    pub course: String,
+    pub score: u32,
+    // Bird height along the course as (distance, y)
+    pub samples: Vec<(f32, f32)>,
+    // Course distance at which each point was scored
+    pub score_marks: Vec<f32>,
+}
+
+impl Replay {
+    // Replays belong to the profile that flew them
+    fn path(profile: &str, name: &str) -> PathBuf {
+        storage::profile_path(profile, REPLAYS_DIR).join(format!("{name}.ron"))
+    }
+
+    pub fn load(profile: &str, name: &str) -> Option<Self> {
+        storage::load(Self::path(profile, name))
+    }
+
+    pub fn save(&self, profile: &str, name: &str) -> io::Result<()> {
+        storage::save(Self::path(profile, name), self)
+    }
+
+    pub fn remove(profile: &str, name: &str) -> io::Result<()> {
+        storage::remove(Self::path(profile, name))
+    }
+
+    // Every saved replay of the profile, personal best first
+    pub fn list(profile: &str) -> Vec<String> {
+        let dir = storage::data_path(storage::profile_path(profile, REPLAYS_DIR));
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+
+        let mut names: Vec<String> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension()? != "ron" {
+                    return None;
+                }
+                Some(path.file_stem()?.to_str()?.to_string())
+            })
+            .collect();
+        names.sort_by_key(|name| (name != BEST_REPLAY, name != LAST_REPLAY, name.clone()));
+        names
+    }
+
+    pub fn display_name(name: &str, language: Language) -> String {
+        match name {
+            BEST_REPLAY => language.translate(Phrase::PersonalBest).to_string(),
+            LAST_REPLAY => language.translate(Phrase::LastRun).to_string(),
+            name => name.replace(['_', '-'], " "),
+        }
+    }
+
+    pub fn course_settings(&self) -> Option<CourseSettings> {
+        CourseSettings::from_code(&self.course).ok()
+    }
+
+    // Bird height at `distance`, or `None` once the recorded run has ended
+    pub fn height_at(&self, distance: f32) -> Option<f32> {
+        let next = self
+            .samples
+            .partition_point(|(sample, _)| *sample <= distance);
+        let (after, after_y) = *self.samples.get(next)?;
+        let Some(&(before, before_y)) = next.checked_sub(1).and_then(|i| self.samples.get(i))
+        else {
+            return Some(after_y);
+        };
+
+        let t = (distance - before) / (after - before).max(f32::EPSILON);
+        Some(before_y.lerp(after_y, t))
+    }
+
+    pub fn score_at(&self, distance: f32) -> u32 {
+        self.score_marks.partition_point(|mark| *mark <= distance) as u32
+    }
+}
+
+// Run being recorded for the personal best and last run replays
+#[derive(Resource, Default)]
+pub struct RunRecorder {
+    pub replay: Replay,
+    pub enabled: bool,
+}
+
+// Replay raced against during the current run
+#[derive(Resource, Default)]
+pub struct ActiveGhost(pub Option<Replay>);
+
+// Replay launched from the leaderboard, raced instead of the one picked in the settings
+#[derive(Resource, Default)]
+pub struct LaunchedReplay(pub Option<Replay>);
+
+impl ActiveGhost {
+    // Ghosts only race on their own course, so entered codes and levels that differ skip them
+    pub fn for_run(
+        settings: &UserSettings,
+        profile: &str,
+        launched: &LaunchedReplay,
+        active_level: &ActiveLevel,
+        course_selection: &CourseSelection,
+    ) -> Self {
+        if active_level.level.is_some() {
+            return Self(None);
+        }
+
+        let replay = launched
+            .0
+            .clone()
+            .or_else(|| Replay::load(profile, settings.ghost.as_deref()?))
+            .filter(|replay| match course_selection {
+                CourseSelection::Random => replay.course_settings().is_some(),
+                CourseSelection::Fixed(course) => course.code() == replay.course,
+            });
+        Self(replay)
+    }
+}
+
+#[derive(Component)]
+pub struct Ghost;
+
+#[derive(Component)]
+pub struct GhostHud;
+
+pub fn spawn_ghost(
+    mut commands: Commands,
+    ghost: Res<ActiveGhost>,
+    bird_textures: Res<BirdTextures>,
+    settings: Res<UserSettings>,
+    root_query: Single<Entity, With<GameWorld>>,
+) {
+    let Some(replay) = &ghost.0 else {
+        return;
+    };
+    let y = replay.height_at(0.0).unwrap_or(70.);
+
+    commands
+        .entity(root_query.into_inner())
+        .with_children(|parent| {
+            // No collider or player marker, so the ghost never touches the real game
+            parent.spawn((
+                Ghost,
+                Sprite {
+                    image: bird_textures.mid.clone(),
+                    color: Color::WHITE.with_alpha(GHOST_ALPHA),
+                    ..default()
+                },
+                Transform::from_xyz(-150., y, Z_POS_PLAYER - 1.0),
+            ));
+        });
+
+    commands.spawn((
+        GhostHud,
+        Node {
+            position_type: PositionType::Absolute,
+            // Below the score row so it stays clear of every player's score
+            top: Val::Px(90.0),
+            right: Val::Px(20.0),
+            ..default()
+        },
+        Text(settings.language.fill(Phrase::GhostScore, &[&replay.score])),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        TextShadow::default(),
+    ));
+}
+
+pub fn cleanup_ghost_hud(mut commands: Commands, query: Query<Entity, With<GhostHud>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn move_ghost(
+    ghost: Res<ActiveGhost>,
+    progress: Res<CourseProgress>,
+    mut ghost_query: Query<(&mut Transform, &mut Visibility), With<Ghost>>,
+) {
+    let Some(replay) = &ghost.0 else {
+        return;
+    };
+
+    for (mut transform, mut visibility) in &mut ghost_query {
+        match replay.height_at(progress.distance) {
+            Some(y) => transform.translation.y = y,
+            // The recorded run is over
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+pub fn update_ghost_hud(
+    ghost: Res<ActiveGhost>,
+    progress: Res<CourseProgress>,
+    score: Res<Score>,
+    settings: Res<UserSettings>,
+    hud_query: Query<(&mut Text, &mut TextColor), With<GhostHud>>,
+) {
+    let Some(replay) = &ghost.0 else {
+        return;
+    };
+
+    let language = settings.language;
+    let ghost_score = replay.score_at(progress.distance);
+    let (label, color) = match score.0.cmp(&ghost_score) {
+        std::cmp::Ordering::Greater => (
+            language.fill(Phrase::AheadOfGhost, &[&(score.0 - ghost_score)]),
+            GHOST_AHEAD_COLOR,
+        ),
+        std::cmp::Ordering::Less => (
+            language.fill(Phrase::BehindGhost, &[&(ghost_score - score.0)]),
+            GHOST_BEHIND_COLOR,
+        ),
+        std::cmp::Ordering::Equal => (
+            language.translate(Phrase::LevelWithGhost).to_string(),
+            Color::WHITE,
+        ),
+    };
+
+    for (mut text, mut text_color) in hud_query {
+        if text.0 != label {
+            text.0.clone_from(&label);
+            text_color.0 = color;
+        }
+    }
+}
+
+pub fn record_run(
+    mut recorder: ResMut<RunRecorder>,
+    progress: Res<CourseProgress>,
+    score: Res<Score>,
+    player_query: Query<(&Transform, &PlayerSlot), With<Player>>,
+) {
+    if !recorder.enabled {
+        return;
+    }
+    let distance = progress.distance;
+    let replay = &mut recorder.replay;
+
+    if let Some((transform, _)) = player_query.iter().find(|(_, slot)| slot.0 == 0) {
+        let due = replay
+            .samples
+            .last()
+            .is_none_or(|(last, _)| distance - last >= SAMPLE_STEP);
+        if due {
+            replay.samples.push((distance, transform.translation.y));
+        }
+    }
+
+    while (replay.score_marks.len() as u32) < score.0 {
+        replay.score_marks.push(distance);
+    }
+}
+
+pub fn save_run(
+    mut recorder: ResMut<RunRecorder>,
+    score: Res<Score>,
+    active: Res<ActiveProfile>,
+    settings: Res<UserSettings>,
+) {
+    if !recorder.enabled {
+        return;
+    }
+    recorder.enabled = false;
+    recorder.replay.score = score.0;
+
+    let replay = &recorder.replay;
+    if let Err(err) = replay.save(&active.0, LAST_REPLAY) {
+        warn!("Failed to save replay: {err}");
+    }
+
+    // Precision scores aren't comparable with one point per pipe, so they never become
+    // the personal best
+    if settings.precision_scoring {
+        return;
+    }
+    let best = Replay::load(&active.0, BEST_REPLAY).map_or(0, |best| best.score);
+    if replay.score > best
+        && let Err(err) = replay.save(&active.0, BEST_REPLAY)
+    {
+        warn!("Failed to save personal best replay: {err}");
+    }
+}
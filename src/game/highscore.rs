@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{components::Score, level::ActiveLevel};
+use crate::profile::ActiveProfile;
+use crate::settings::UserSettings;
+use crate::storage;
+
+pub const HIGH_SCORE_FILE: &str = "highscore.ron";
+
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct HighScore {
+    pub best: u32,
+    // Whether the run that just ended set the current best
+    #[serde(skip)]
+    pub new_best: bool,
+}
+
+impl HighScore {
+    pub fn load(profile: &str) -> Self {
+        storage::load(storage::profile_path(profile, HIGH_SCORE_FILE)).unwrap_or_default()
+    }
+
+    pub fn save(&self, profile: &str) {
+        if let Err(err) = storage::save(storage::profile_path(profile, HIGH_SCORE_FILE), self) {
+            warn!("Failed to save high score: {err}");
+        }
+    }
+}
+
+// Runs before the game-over overlay so it can show the "New best!" highlight
+pub fn update_high_score(
+    score: Res<Score>,
+    active_level: Res<ActiveLevel>,
+    settings: Res<UserSettings>,
+    mut high_score: ResMut<HighScore>,
+    active: Res<ActiveProfile>,
+) {
+    // Test-plays from the editor don't count, and precision points aren't comparable
+    // with a pipe count
+    high_score.new_best =
+        !active_level.playtest && !settings.precision_scoring && score.0 > high_score.best;
+    if high_score.new_best {
+        high_score.best = score.0;
+        high_score.save(&active.0);
+    }
+}
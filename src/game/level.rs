@@ -0,0 +1,96 @@
+use std::fs;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+pub const LEVELS_DIR: &str = "levels";
+pub const DEFAULT_LEVEL_NAME: &str = "custom";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PipeVariant {
+    #[default]
+    Green,
+    Red,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum PipeMovement {
+    #[default]
+    Static,
+    Oscillate { amplitude: f32, speed: f32 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PipeSpec {
+    // Position along the course, measured in the same units as pipe movement
+    pub x: f32,
+    pub gap_y: f32,
+    pub gap_size: f32,
+    pub variant: PipeVariant,
+    pub movement: PipeMovement,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Level {
+    pub name: String,
+    pub pipes: Vec<PipeSpec>,
+}
+
+impl Level {
+    pub fn sort(&mut self) {
+        self.pipes.sort_by(|a, b| a.x.total_cmp(&b.x));
+    }
+
+    pub fn load(name: &str) -> Option<Self> {
+        let mut level: Level = storage::load(format!("{LEVELS_DIR}/{name}.ron"))?;
+        level.sort();
+        Some(level)
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        storage::save(format!("{LEVELS_DIR}/{}.ron", self.name), self)
+    }
+
+    // Names of the saved levels, in alphabetical order
+    pub fn list() -> Vec<String> {
+        let Ok(entries) = fs::read_dir(storage::data_path(LEVELS_DIR)) else {
+            return Vec::new();
+        };
+
+        let mut names: Vec<String> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "ron"))
+            .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+// Level being played instead of randomly generated pipes
+#[derive(Resource, Default)]
+pub struct ActiveLevel {
+    pub level: Option<Level>,
+    pub start_distance: f32,
+    pub playtest: bool,
+}
+
+#[derive(Resource, Default)]
+pub struct CourseProgress {
+    pub distance: f32,
+    pub next_pipe: usize,
+}
+
+#[derive(Component, Clone)]
+pub struct PipeMotion {
+    pub movement: PipeMovement,
+    pub base_y: f32,
+    pub elapsed: f32,
+}
+
+pub fn level_active(active_level: Res<ActiveLevel>) -> bool {
+    active_level.level.is_some()
+}
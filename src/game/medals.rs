@@ -0,0 +1,185 @@
+use bevy::{ecs::spawn::SpawnIter, prelude::*};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    components::PipesPassed,
+    config::{GameConfig, MedalConfig},
+    constants::*,
+    level::ActiveLevel,
+};
+use crate::locale::{Language, Phrase};
+use crate::profile::Profile;
+
+const MEDAL_SIZE: f32 = 66.0;
+// The medal pops in once the game-over panel has settled
+const REVEAL_DELAY: f32 = 0.4;
+const REVEAL_DURATION: f32 = 0.5;
+const SPARKLE_SIZE: f32 = 6.0;
+const SPARKLE_PERIODS: [f32; 3] = [0.6, 0.75, 0.9];
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Medal {
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+}
+
+impl Medal {
+    pub const ALL: [Medal; 4] = [Self::Bronze, Self::Silver, Self::Gold, Self::Platinum];
+
+    pub fn phrase(&self) -> Phrase {
+        match self {
+            Self::Bronze => Phrase::BronzeMedal,
+            Self::Silver => Phrase::SilverMedal,
+            Self::Gold => Phrase::GoldMedal,
+            Self::Platinum => Phrase::PlatinumMedal,
+        }
+    }
+
+    pub fn sprite_path(&self) -> &'static str {
+        match self {
+            Self::Bronze => "sprites/medal-bronze.png",
+            Self::Silver => "sprites/medal-silver.png",
+            Self::Gold => "sprites/medal-gold.png",
+            Self::Platinum => "sprites/medal-platinum.png",
+        }
+    }
+}
+
+impl MedalConfig {
+    fn threshold(&self, medal: Medal) -> u32 {
+        match medal {
+            Medal::Bronze => self.bronze,
+            Medal::Silver => self.silver,
+            Medal::Gold => self.gold,
+            Medal::Platinum => self.platinum,
+        }
+    }
+
+    // Best medal the score earns, if any
+    pub fn medal(&self, score: u32) -> Option<Medal> {
+        Medal::ALL
+            .into_iter()
+            .rev()
+            .find(|medal| score >= self.threshold(*medal))
+    }
+}
+
+#[derive(Component)]
+pub struct MedalReveal {
+    pub elapsed: f32,
+}
+
+#[derive(Component)]
+pub struct MedalSparkle {
+    pub timer: Timer,
+}
+
+pub struct MedalPlugin;
+
+impl Plugin for MedalPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::GameOver), award_medal)
+            .add_systems(
+                Update,
+                (reveal_medal, animate_sparkles).run_if(in_state(AppState::GameOver)),
+            );
+    }
+}
+
+// Medal shown on the game-over panel
+pub fn medal_widget(asset_server: &AssetServer, medal: Medal, language: Language) -> impl Bundle {
+    (
+        Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            margin: UiRect::bottom(Val::Px(10.0)),
+            ..default()
+        },
+        MedalReveal { elapsed: 0.0 },
+        Transform::from_scale(Vec3::ZERO),
+        children![
+            (
+                Node {
+                    width: Val::Px(MEDAL_SIZE),
+                    height: Val::Px(MEDAL_SIZE),
+                    ..default()
+                },
+                ImageNode::new(asset_server.load(medal.sprite_path())),
+                Children::spawn(SpawnIter(SPARKLE_PERIODS.into_iter().map(sparkle))),
+            ),
+            (
+                Text(language.translate(medal.phrase()).to_string()),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextShadow::default(),
+            ),
+        ],
+    )
+}
+
+fn sparkle(period: f32) -> impl Bundle {
+    (
+        MedalSparkle {
+            timer: Timer::from_seconds(period, TimerMode::Repeating),
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Percent(40.0),
+            top: Val::Percent(40.0),
+            width: Val::Px(SPARKLE_SIZE),
+            height: Val::Px(SPARKLE_SIZE),
+            ..default()
+        },
+        BackgroundColor(Color::WHITE.with_alpha(0.0)),
+        Transform::from_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+    )
+}
+
+fn award_medal(
+    pipes_passed: Res<PipesPassed>,
+    config: Res<GameConfig>,
+    active_level: Res<ActiveLevel>,
+    mut profile: ResMut<Profile>,
+) {
+    if active_level.playtest {
+        return;
+    }
+    if let Some(medal) = config.medals.medal(pipes_passed.0) {
+        *profile.medals.entry(medal).or_default() += 1;
+    }
+}
+
+fn reveal_medal(time: Res<Time>, mut medal_query: Query<(&mut MedalReveal, &mut Transform)>) {
+    for (mut reveal, mut transform) in &mut medal_query {
+        if reveal.elapsed > REVEAL_DELAY + REVEAL_DURATION {
+            continue;
+        }
+        reveal.elapsed += time.delta_secs();
+
+        let t = ((reveal.elapsed - REVEAL_DELAY) / REVEAL_DURATION).clamp(0.0, 1.0);
+        transform.scale = Vec3::splat(EaseFunction::BackOut.sample_clamped(t));
+    }
+}
+
+// Each sparkle flashes at a random spot on the medal, then moves on
+fn animate_sparkles(
+    time: Res<Time>,
+    mut sparkle_query: Query<(&mut MedalSparkle, &mut Node, &mut BackgroundColor)>,
+) {
+    let mut rng = rand::rng();
+    for (mut sparkle, mut node, mut color) in &mut sparkle_query {
+        if sparkle.timer.tick(time.delta()).just_finished() {
+            let max = MEDAL_SIZE * 0.8 - SPARKLE_SIZE;
+            node.left = Val::Px(rng.random_range(MEDAL_SIZE * 0.1..max));
+            node.top = Val::Px(rng.random_range(MEDAL_SIZE * 0.1..max));
+        }
+
+        let flash = (sparkle.timer.fraction() * std::f32::consts::PI).sin();
+        color.0.set_alpha(flash);
+    }
+}
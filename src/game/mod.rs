@@ -1,17 +1,30 @@
+pub mod animation;
 pub mod audio;
+pub mod coins;
 pub mod collision;
 pub mod components;
 pub mod config;
 pub mod constants;
+pub mod course;
 pub mod events;
+pub mod ghost;
+pub mod highscore;
+pub mod level;
+pub mod medals;
+pub mod music;
 pub mod player;
 pub mod pipes;
+pub mod powerups;
+pub mod precision;
 pub mod score;
+pub mod skins;
+pub mod sound_packs;
 pub mod systems;
+pub mod trails;
 pub mod ui;
 
 pub use components::*;
 pub use constants::*;
 pub use config::*;
 pub use events::*;
-pub use systems::*; 
\ No newline at end of file
+pub use systems::*;
@@ -0,0 +1,250 @@
+use bevy::{asset::io::file::FileAssetReader, audio::Volume, prelude::*};
+
+use crate::game::{AppState, Difficulty, GameplayEvent, PlayPhase, audio::AudioChannel};
+use crate::settings::UserSettings;
+
+// Shared music for any track whose own file is missing
+const MUSIC_PATH: &str = "audio/random_game_music.ogg";
+// Seconds for a track to fade all the way in or out
+const FADE_TIME: f32 = 1.2;
+// Share of the music level left while the game is paused
+const PAUSE_LEVEL: f32 = 0.3;
+// Each difficulty level speeds the gameplay music up by this much, up to a cap
+const INTENSITY_SPEED_STEP: f32 = 0.03;
+const MAX_INTENSITY: u32 = 5;
+// Seconds the music stays ducked after a close call
+const DUCK_TIME: f32 = 2.0;
+// Bevy's audio sinks have no filters, so ducking only lowers the volume
+const DUCK_LEVEL: f32 = 0.4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MusicTrack {
+    Menu,
+    Gameplay,
+    GameOver,
+}
+
+impl MusicTrack {
+    fn for_state(state: AppState) -> Self {
+        match state {
+            AppState::InGame => Self::Gameplay,
+            AppState::GameOver => Self::GameOver,
+            _ => Self::Menu,
+        }
+    }
+
+    fn own_path(&self) -> &'static str {
+        match self {
+            Self::Menu => "audio/music_menu.ogg",
+            Self::Gameplay => "audio/music_gameplay.ogg",
+            Self::GameOver => "audio/music_game_over.ogg",
+        }
+    }
+
+    fn has_own_file(&self) -> bool {
+        FileAssetReader::get_base_path()
+            .join("assets")
+            .join(self.own_path())
+            .is_file()
+    }
+
+    fn path(&self) -> &'static str {
+        if self.has_own_file() {
+            self.own_path()
+        } else {
+            MUSIC_PATH
+        }
+    }
+
+    // Tracks sharing the fallback music play it slower on the menu and game over
+    // screens to set them apart
+    fn speed(&self) -> f32 {
+        if self.has_own_file() {
+            return 1.0;
+        }
+        match self {
+            Self::Menu => 0.85,
+            Self::Gameplay => 1.0,
+            Self::GameOver => 0.7,
+        }
+    }
+}
+
+// A playing track and how far it has faded in
+#[derive(Component, Debug)]
+#[require(AudioChannel::Music)]
+pub struct Music {
+    pub track: MusicTrack,
+    pub level: f32,
+    pub target: f32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MusicMood {
+    #[default]
+    Steady,
+    // Seconds left before the music comes back up after a close call
+    Ducked(f32),
+}
+
+// How the gameplay track reacts to the run
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub struct AdaptiveMusic {
+    pub intensity: u32,
+    pub mood: MusicMood,
+}
+
+impl AdaptiveMusic {
+    fn level(&self) -> f32 {
+        match self.mood {
+            MusicMood::Steady => 1.0,
+            MusicMood::Ducked(_) => DUCK_LEVEL,
+        }
+    }
+
+    fn speed(&self) -> f32 {
+        1.0 + self.intensity as f32 * INTENSITY_SPEED_STEP
+    }
+}
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AdaptiveMusic>().add_systems(
+            Update,
+            (update_adaptive_music, choose_track, fade_music).chain(),
+        );
+    }
+}
+
+// Close calls duck the music for a moment; dying brings it straight back up
+fn update_adaptive_music(
+    time: Res<Time<Real>>,
+    difficulty: Res<Difficulty>,
+    mut gameplay_events: EventReader<GameplayEvent>,
+    mut adaptive: ResMut<AdaptiveMusic>,
+) {
+    let mut next = *adaptive;
+    next.intensity = difficulty.current_level.min(MAX_INTENSITY);
+    if let MusicMood::Ducked(remaining) = next.mood {
+        let remaining = remaining - time.delta_secs();
+        next.mood = if remaining > 0.0 {
+            MusicMood::Ducked(remaining)
+        } else {
+            MusicMood::Steady
+        };
+    }
+
+    for event in gameplay_events.read() {
+        match event {
+            GameplayEvent::NearMiss { .. } => next.mood = MusicMood::Ducked(DUCK_TIME),
+            GameplayEvent::Death { .. } => next.mood = MusicMood::Steady,
+            _ => {}
+        }
+    }
+
+    // The countdown itself doesn't count as a change
+    if next.intensity != adaptive.intensity
+        || std::mem::discriminant(&next.mood) != std::mem::discriminant(&adaptive.mood)
+    {
+        *adaptive = next;
+    } else {
+        adaptive.bypass_change_detection().mood = next.mood;
+    }
+}
+
+// The track of the current screen fades in while every other one fades out
+fn choose_track(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    app_state: Res<State<AppState>>,
+    play_phase: Option<Res<State<PlayPhase>>>,
+    adaptive: Res<AdaptiveMusic>,
+    mut music_query: Query<&mut Music>,
+) {
+    let wanted = MusicTrack::for_state(*app_state.get());
+    let paused = play_phase.is_some_and(|phase| *phase.get() == PlayPhase::Paused);
+    let level = if paused {
+        PAUSE_LEVEL
+    } else if wanted == MusicTrack::Gameplay {
+        adaptive.level()
+    } else {
+        1.0
+    };
+
+    let mut playing = false;
+    for mut music in &mut music_query {
+        let target = if music.track == wanted {
+            playing = true;
+            level
+        } else {
+            0.0
+        };
+        if music.target != target {
+            music.target = target;
+        }
+    }
+
+    if !playing {
+        commands.spawn((
+            AudioPlayer::new(asset_server.load(wanted.path())),
+            PlaybackSettings::LOOP
+                .with_volume(Volume::Linear(0.0))
+                .with_speed(wanted.speed()),
+            Music {
+                track: wanted,
+                level: 0.0,
+                target: level,
+            },
+        ));
+    }
+}
+
+fn fade_music(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    settings: Res<UserSettings>,
+    adaptive: Res<AdaptiveMusic>,
+    app_state: Res<State<AppState>>,
+    mut music_query: Query<(Entity, &mut Music, Option<&mut AudioSink>)>,
+) {
+    let step = time.delta_secs() / FADE_TIME;
+    for (entity, mut music, sink) in &mut music_query {
+        let faded = music.level != music.target;
+        if faded {
+            music.level = if music.level < music.target {
+                (music.level + step).min(music.target)
+            } else {
+                (music.level - step).max(music.target)
+            };
+        }
+
+        if music.level == 0.0 && music.target == 0.0 {
+            // The gameplay track waits out the game over screen so Retry picks it
+            // back up where it left off
+            if music.track == MusicTrack::Gameplay && *app_state.get() == AppState::GameOver {
+                if let Some(sink) = sink {
+                    sink.pause();
+                }
+            } else {
+                commands.entity(entity).despawn();
+            }
+            continue;
+        }
+
+        let Some(mut sink) = sink else {
+            continue;
+        };
+        if sink.is_paused() {
+            sink.play();
+        }
+        if faded || settings.is_changed() || sink.is_added() {
+            let volume = settings.audio.channel(AudioChannel::Music) * music.level;
+            sink.set_volume(Volume::Linear(volume));
+        }
+        if music.track == MusicTrack::Gameplay && (adaptive.is_changed() || sink.is_added()) {
+            sink.set_speed(adaptive.speed());
+        }
+    }
+}
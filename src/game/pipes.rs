@@ -2,29 +2,92 @@ use std::f32::consts::PI;
 use bevy::prelude::*;
 use rand::Rng;
 use crate::game::{
+    AppState,
     constants::*,
     components::*,
+    config::GameConfig,
+    course::Course,
+    level::*,
+    powerups::WorldSpeed,
 };
 
+pub fn spawn_pipe_pair(
+    parent: &mut ChildSpawnerCommands,
+    pipe_textures: &PipeTextures,
+    x: f32,
+    spec: &PipeSpec,
+) -> Entity {
+    let image = match spec.variant {
+        PipeVariant::Green => pipe_textures.green_pipe.clone(),
+        PipeVariant::Red => pipe_textures.red_pipe.clone(),
+    };
+
+    let pipe_offset = spec.gap_size / 2.0 + PIPE_HEIGHT / 2.0;
+    parent
+        .spawn((
+            PipePair {
+                gap_size: spec.gap_size,
+                ..default()
+            },
+            PipeMotion {
+                movement: spec.movement,
+                base_y: spec.gap_y,
+                elapsed: 0.0,
+            },
+            Transform::from_xyz(x, spec.gap_y, Z_POS_PIPE),
+            Visibility::Visible,
+            children![
+                (
+                    Pipe,
+                    Sprite {
+                        image: image.clone(),
+                        ..default()
+                    },
+                    Transform::from_xyz(0., -pipe_offset, 0.,),
+                    Collider,
+                ),
+                (
+                    Pipe,
+                    Sprite {
+                        image,
+                        ..default()
+                    },
+                    Transform {
+                        translation: Vec3::new(0., pipe_offset, 0.,),
+                        rotation: Quat::from_rotation_x(PI),
+                        ..default()
+                    },
+                    Collider,
+                )
+            ],
+        ))
+        .id()
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn generate_pipes(
     mut commands: Commands,
     pipe_textures: Res<PipeTextures>,
     mut interval: ResMut<PipeInterval>,
+    mut course: ResMut<Course>,
+    config: Res<GameConfig>,
     difficulty: Res<Difficulty>,
     time: Res<Time>,
+    world_speed: Res<WorldSpeed>,
     root_query: Query<Entity, With<GameWorld>>,
 ) {
     let root = root_query.single().expect("Game scene not found");
 
-    interval.0.tick(time.delta());
+    // Spawns slow down with the pipes so their spacing stays the same
+    interval.0.tick(time.delta().mul_f32(world_speed.0));
     if interval.0.finished() {
         interval.0.reset();
-        
+
         // Update the interval timer with current difficulty for next spawn
-        interval.update_interval(INITIAL_PIPE_INTERVAL, difficulty.spawn_interval_multiplier);
+        interval.update_interval(config.pipes.spawn_interval, difficulty.spawn_interval_multiplier);
 
         // Apply difficulty multiplier to pipe gap (starts large, gets smaller)
-        let base_gap = rand::rng().random_range(MIN_PIPE_GAP..MAX_PIPE_GAP);
+        let base_gap = course.rng.random_range(config.pipes.min_gap..=config.pipes.max_gap);
         let pipe_gap = base_gap * difficulty.pipe_gap_multiplier;
 
         // Ensure pipe gap doesn't get too small to avoid invalid ranges
@@ -32,67 +95,116 @@ pub fn generate_pipes(
         let safe_pipe_gap = pipe_gap.max(min_gap);
 
         // Calculate the valid range for pipe positioning
-        let min_y = -BG_IMG_DIMENSIONS.1 / 2.0 + safe_pipe_gap / 2.0 + PIPE_LEGROOM;
-        let max_y = BG_IMG_DIMENSIONS.1 / 2.0 - safe_pipe_gap / 2.0 - PIPE_LEGROOM;
-        
+        let min_y = -BG_IMG_DIMENSIONS.1 / 2.0 + safe_pipe_gap / 2.0 + config.pipes.legroom;
+        let max_y = BG_IMG_DIMENSIONS.1 / 2.0 - safe_pipe_gap / 2.0 - config.pipes.legroom;
+
         // Ensure the range is valid
         let new_y = if min_y >= max_y {
             // If range is invalid, use a default position
             0.0
         } else {
-            rand::rng().random_range(min_y..max_y)
+            course.rng.random_range(min_y..max_y)
         };
 
-        let pipe_offset = safe_pipe_gap / 2.0 + PIPE_HEIGHT / 2.0;
+        let spec = PipeSpec {
+            x: PIPE_SPAWN_X,
+            gap_y: new_y,
+            gap_size: safe_pipe_gap,
+            variant: PipeVariant::Green,
+            movement: PipeMovement::Static,
+        };
         commands.entity(root).with_children(|parent| {
-            parent.spawn((
-                PipePair::default(),
-                Transform::from_xyz(BG_IMG_DIMENSIONS.0 + PIPE_WIDTH / 2.0, new_y, Z_POS_PIPE),
-                Visibility::Visible,
-                children![
-                    (
-                        Pipe,
-                        Sprite {
-                            image: pipe_textures.green_pipe.clone(),
-                            ..default()
-                        },
-                        Transform::from_xyz(0., -pipe_offset, 0.,),
-                        Collider,
-                    ),
-                    (
-                        Pipe,
-                        Sprite {
-                            image: pipe_textures.green_pipe.clone(),
-                            ..default()
-                        },
-                        Transform {
-                            translation: Vec3::new(0., pipe_offset, 0.,),
-                            rotation: Quat::from_rotation_x(PI),
-                            ..default()
-                        },
-                        Collider,
-                    )
-                ],
-            ));
+            spawn_pipe_pair(parent, &pipe_textures, PIPE_SPAWN_X, &spec);
         });
     }
 }
 
+pub fn spawn_level_pipes(
+    mut commands: Commands,
+    pipe_textures: Res<PipeTextures>,
+    active_level: Res<ActiveLevel>,
+    mut progress: ResMut<CourseProgress>,
+    root_query: Single<Entity, With<GameWorld>>,
+) {
+    let Some(level) = &active_level.level else {
+        return;
+    };
+    let root = root_query.into_inner();
+
+    while let Some(spec) = level.pipes.get(progress.next_pipe) {
+        let x = spec.x - progress.distance;
+        if x > PIPE_SPAWN_X {
+            break;
+        }
+        progress.next_pipe += 1;
+
+        // Skip pipes that are already behind the screen when starting mid-course
+        if x < PIPE_DESPAWN_X {
+            continue;
+        }
+
+        commands.entity(root).with_children(|parent| {
+            spawn_pipe_pair(parent, &pipe_textures, x, spec);
+        });
+    }
+}
+
+// A level ends once its last pipe has been spawned and passed
+pub fn finish_level(
+    active_level: Res<ActiveLevel>,
+    progress: Res<CourseProgress>,
+    pipe_query: Query<&PipePair>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    let Some(level) = &active_level.level else {
+        return;
+    };
+    if progress.next_pipe < level.pipes.len() || pipe_query.iter().any(|pipe| !pipe.scored) {
+        return;
+    }
+
+    app_state.set(if active_level.playtest {
+        AppState::Editor
+    } else {
+        AppState::GameOver
+    });
+}
+
 pub fn move_pipes(
+    time: Res<Time>,
     mut query: Query<(&mut Transform, &PipePair)>,
+    mut progress: ResMut<CourseProgress>,
+    config: Res<GameConfig>,
     difficulty: Res<Difficulty>,
+    world_speed: Res<WorldSpeed>,
 ) {
+    // Apply difficulty multiplier to pipe speed
+    let speed =
+        config.pipes.speed * difficulty.pipe_speed_multiplier * world_speed.0 * time.delta_secs();
+    progress.distance += speed;
+
     for (mut transform, _) in &mut query {
-        // Apply difficulty multiplier to pipe speed
-        transform.translation.x -= PIPE_SPEED * difficulty.pipe_speed_multiplier;
+        transform.translation.x -= speed;
+    }
+}
+
+pub fn animate_pipes(
+    time: Res<Time>,
+    world_speed: Res<WorldSpeed>,
+    mut query: Query<(&mut Transform, &mut PipeMotion)>,
+) {
+    for (mut transform, mut motion) in &mut query {
+        if let PipeMovement::Oscillate { amplitude, speed } = motion.movement {
+            motion.elapsed += time.delta_secs() * world_speed.0;
+            transform.translation.y = motion.base_y + (motion.elapsed * speed).sin() * amplitude;
+        }
     }
 }
 
 pub fn destroy_pipes(mut commands: Commands, query: Query<(Entity, &Transform), With<Pipe>>) {
-    let threshold = -GAME_DIMENSIONS.0 / 2.0 - PIPE_WIDTH / 2.0;
     for (entity, transform) in &query {
-        if transform.translation.x < threshold {
+        if transform.translation.x < PIPE_DESPAWN_X {
             commands.entity(entity).despawn();
         }
     }
-} 
\ No newline at end of file
+}
@@ -1,18 +1,21 @@
 use bevy::prelude::*;
+use crate::input::{ActionState, GameAction};
 use crate::game::{
+    animation::FlapAnimation,
     constants::*,
     components::*,
-    events::AudioEvent,
+    config::GameConfig,
+    events::{AudioEvent, DeathCause, GameplayEvent, Sound},
 };
 
 pub fn apply_gravity(
-    textures: Res<BirdTextures>,
+    config: Res<GameConfig>,
     time: Res<Time>,
-    mut player_query: Query<(&mut Sprite, &mut Transform, &mut Velocity), With<Player>>,
+    mut player_query: Query<(&mut Transform, &mut Velocity), With<Player>>,
 ) {
-    for (mut sprite, mut transform, mut velocity) in &mut player_query {
-        **velocity += GRAVITY * time.delta_secs();
-        **velocity = (**velocity).max(MAX_FALL_SPEED);
+    for (mut transform, mut velocity) in &mut player_query {
+        **velocity += config.player.gravity * time.delta_secs();
+        **velocity = (**velocity).max(config.player.max_fall_speed);
 
         transform.translation.y += **velocity * time.delta_secs();
 
@@ -22,46 +25,103 @@ pub fn apply_gravity(
         }
 
         transform.rotation =
-            Quat::from_rotation_z((**velocity / MAX_FALL_SPEED.abs() * 1.5).clamp(
-                -MAX_PLAYER_ROTATION.to_radians(),
-                MAX_PLAYER_ROTATION.to_radians(),
+            Quat::from_rotation_z((**velocity / config.player.max_fall_speed.abs() * 1.5).clamp(
+                -config.player.max_rotation.to_radians(),
+                config.player.max_rotation.to_radians(),
             ));
+    }
+}
 
-        sprite.image = if **velocity > 150.0 {
-            textures.up.clone()
-        } else if **velocity < -150.0 {
-            textures.down.clone()
-        } else {
-            textures.mid.clone()
-        };
+pub fn hover_player(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut player_query: Query<(&mut Transform, &PlayerSlot), With<Player>>,
+) {
+    // Gentle bob on the Get Ready screen
+    let offset = (time.elapsed_secs() * GET_READY_BOB_SPEED).sin() * GET_READY_BOB_HEIGHT;
+    for (mut transform, slot) in &mut player_query {
+        transform.translation.y =
+            config.player.initial_position.y - slot.0 as f32 * PLAYER_SPAWN_SPACING + offset;
     }
 }
 
 pub fn handle_jump_input(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut player_query: Query<&mut Velocity, With<Player>>,
+    actions: Res<ActionState>,
+    config: Res<GameConfig>,
+    phase: Res<State<PlayPhase>>,
+    mut next_phase: ResMut<NextState<PlayPhase>>,
+    mut player_query: ActiveBirds<(&PlayerSlot, &Transform, &mut Velocity, &mut FlapAnimation)>,
     mut audio_events: EventWriter<AudioEvent>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
 ) {
-    // Jump
-    if keyboard.just_pressed(KeyCode::Space) {
-        for mut velocity in &mut player_query {
-            *velocity = Velocity(JUMP_IMPULSE)
+    let mut flapped_at = None;
+
+    // Jump, each bird on its own player's flap action
+    for (slot, transform, mut velocity, mut animation) in &mut player_query {
+        if actions.just_pressed(GameAction::flap(slot.0)) {
+            *velocity = Velocity(config.player.jump_impulse);
+            animation.boost();
+            gameplay_events.write(GameplayEvent::Flap { slot: slot.0 });
+            flapped_at = Some(transform.translation.truncate());
+        }
+    }
+
+    if let Some(position) = flapped_at {
+        if *phase.get() == PlayPhase::GetReady {
+            next_phase.set(PlayPhase::Playing);
         }
         // Send wing sound event
-        audio_events.write(AudioEvent::Wing);
+        audio_events.write(AudioEvent::new(Sound::Wing, position));
+    }
+}
+
+pub fn handle_pause_input(
+    actions: Res<ActionState>,
+    phase: Res<State<PlayPhase>>,
+    mut next_phase: ResMut<NextState<PlayPhase>>,
+) {
+    match phase.get() {
+        PlayPhase::Playing if actions.just_pressed(GameAction::Pause) => {
+            next_phase.set(PlayPhase::Paused);
+        }
+        PlayPhase::Paused
+            if actions.any_just_pressed([GameAction::Pause, GameAction::Confirm]) =>
+        {
+            next_phase.set(PlayPhase::Playing);
+        }
+        _ => {}
     }
 }
 
 pub fn detect_gameover(
-    player_query: Single<&Transform, With<Player>>,
-    mut app_state: ResMut<NextState<AppState>>,
+    mut commands: Commands,
+    time: Res<Time>,
+    player_query: ActiveBirds<(Entity, &Transform, &PlayerSlot)>,
     mut audio_events: EventWriter<AudioEvent>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
 ) {
-    let transform = player_query.into_inner();
+    for (entity, transform, slot) in &player_query {
+        if transform.translation.y < -BG_IMG_DIMENSIONS.1 / 2.0 - 30.0 {
+            // Send die sound event
+            audio_events.write(AudioEvent::new(
+                Sound::Die,
+                transform.translation.truncate(),
+            ));
+            gameplay_events.write(GameplayEvent::Death {
+                slot: slot.0,
+                cause: DeathCause::Ground,
+                position: transform.translation.truncate(),
+            });
+            commands.entity(entity).insert(Eliminated {
+                at: time.elapsed_secs(),
+            });
+        }
+    }
+}
 
-    if transform.translation.y < -BG_IMG_DIMENSIONS.1 / 2.0 - 30.0 {
-        // Send die sound event
-        audio_events.write(AudioEvent::Die);
+// The round is over once every bird has been eliminated
+pub fn end_round(player_query: ActiveBirds<()>, mut app_state: ResMut<NextState<AppState>>) {
+    if player_query.is_empty() {
         app_state.set(AppState::GameOver);
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,475 @@
+use bevy::{ecs::component::Mutable, prelude::*};
+use rand::Rng;
+
+use crate::game::{
+    collision::check_collision,
+    components::*,
+    config::GameConfig,
+    constants::*,
+    course::Course,
+    events::{AudioEvent, Sound},
+    level::level_active,
+};
+use crate::locale::{Language, Phrase};
+use crate::settings::UserSettings;
+
+const POWER_UP_CHANCE: f64 = 0.2;
+const POWER_UP_SIZE: Vec2 = Vec2::splat(24.0);
+
+const SHIELD_DURATION: f32 = 12.0;
+const MAX_SHIELD_CHARGES: u32 = 2;
+// Time a bird can pass through pipes after its shield absorbed a hit
+const HIT_GRACE_DURATION: f32 = 2.5;
+const SLOW_MOTION_DURATION: f32 = 5.0;
+const MAX_SLOW_MOTION_DURATION: f32 = 10.0;
+const SLOW_MOTION_WORLD_SPEED: f32 = 0.5;
+const SHRINK_DURATION: f32 = 8.0;
+const SHRINK_SCALE: f32 = 0.5;
+const MAGNET_DURATION: f32 = 10.0;
+const MAGNET_RADIUS: f32 = 140.0;
+const MAGNET_PULL_SPEED: f32 = 260.0;
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PowerUp {
+    Shield,
+    SlowMotion,
+    Shrink,
+    Magnet,
+}
+
+impl PowerUp {
+    pub const ALL: [PowerUp; 4] = [Self::Shield, Self::SlowMotion, Self::Shrink, Self::Magnet];
+
+    pub fn sprite_path(&self) -> &'static str {
+        match self {
+            Self::Shield => "sprites/powerup-shield.png",
+            Self::SlowMotion => "sprites/powerup-slow.png",
+            Self::Shrink => "sprites/powerup-shrink.png",
+            Self::Magnet => "sprites/powerup-magnet.png",
+        }
+    }
+
+    fn grant(&self, commands: &mut Commands, bird: Entity) {
+        match self {
+            Self::Shield => grant::<Shield>(commands, bird),
+            Self::SlowMotion => grant::<SlowMotion>(commands, bird),
+            Self::Shrink => grant::<Shrink>(commands, bird),
+            Self::Magnet => grant::<Magnet>(commands, bird),
+        }
+    }
+}
+
+// Anything the magnet pulls towards the bird
+#[derive(Component, Default)]
+pub struct Collectible;
+
+// How fast pipes, backgrounds and pipe spawns advance, slowed down by slow motion
+#[derive(Resource, Deref, DerefMut, PartialEq)]
+pub struct WorldSpeed(pub f32);
+
+impl Default for WorldSpeed {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+#[derive(Component)]
+pub struct PowerUpHud;
+
+// Timed effect on a bird, removed once its timer runs out
+pub trait Effect: Component<Mutability = Mutable> {
+    const NAME: Phrase;
+
+    fn new() -> Self;
+    fn timer(&self) -> &Timer;
+    fn timer_mut(&mut self) -> &mut Timer;
+
+    // Picking up the same power-up again restarts the effect unless it says otherwise
+    fn stack(&mut self) {
+        self.timer_mut().reset();
+    }
+}
+
+#[derive(Component)]
+pub struct Shield {
+    pub timer: Timer,
+    pub charges: u32,
+}
+
+impl Shield {
+    // Uses up a charge, returns whether the shield is gone
+    pub fn absorb(&mut self) -> bool {
+        self.charges = self.charges.saturating_sub(1);
+        self.charges == 0
+    }
+}
+
+impl Effect for Shield {
+    const NAME: Phrase = Phrase::Shield;
+
+    fn new() -> Self {
+        Self {
+            timer: Timer::from_seconds(SHIELD_DURATION, TimerMode::Once),
+            charges: 1,
+        }
+    }
+
+    fn timer(&self) -> &Timer {
+        &self.timer
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.timer
+    }
+
+    fn stack(&mut self) {
+        self.timer.reset();
+        self.charges = (self.charges + 1).min(MAX_SHIELD_CHARGES);
+    }
+}
+
+#[derive(Component)]
+pub struct SlowMotion(pub Timer);
+
+impl Effect for SlowMotion {
+    const NAME: Phrase = Phrase::SlowMotion;
+
+    fn new() -> Self {
+        Self(Timer::from_seconds(SLOW_MOTION_DURATION, TimerMode::Once))
+    }
+
+    fn timer(&self) -> &Timer {
+        &self.0
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.0
+    }
+
+    // Extra pickups add to the remaining time, up to a cap
+    fn stack(&mut self) {
+        let remaining =
+            (self.0.remaining_secs() + SLOW_MOTION_DURATION).min(MAX_SLOW_MOTION_DURATION);
+        self.0 = Timer::from_seconds(remaining, TimerMode::Once);
+    }
+}
+
+#[derive(Component)]
+pub struct Shrink(pub Timer);
+
+impl Effect for Shrink {
+    const NAME: Phrase = Phrase::Shrink;
+
+    fn new() -> Self {
+        Self(Timer::from_seconds(SHRINK_DURATION, TimerMode::Once))
+    }
+
+    fn timer(&self) -> &Timer {
+        &self.0
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.0
+    }
+}
+
+#[derive(Component)]
+pub struct Magnet(pub Timer);
+
+impl Effect for Magnet {
+    const NAME: Phrase = Phrase::Magnet;
+
+    fn new() -> Self {
+        Self(Timer::from_seconds(MAGNET_DURATION, TimerMode::Once))
+    }
+
+    fn timer(&self) -> &Timer {
+        &self.0
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.0
+    }
+}
+
+#[derive(Component)]
+pub struct HitGrace(pub Timer);
+
+impl Effect for HitGrace {
+    const NAME: Phrase = Phrase::Grace;
+
+    fn new() -> Self {
+        Self(Timer::from_seconds(HIT_GRACE_DURATION, TimerMode::Once))
+    }
+
+    fn timer(&self) -> &Timer {
+        &self.0
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.0
+    }
+}
+
+pub fn grant<T: Effect>(commands: &mut Commands, bird: Entity) {
+    commands
+        .entity(bird)
+        .queue(|mut entity: EntityWorldMut| match entity.get_mut::<T>() {
+            Some(mut effect) => effect.stack(),
+            None => {
+                entity.insert(T::new());
+            }
+        });
+}
+
+// Collider of a bird with its current effects applied
+pub fn bird_collision_size(config: &GameConfig, shrink: Option<&Shrink>) -> Vec2 {
+    match shrink {
+        Some(_) => config.player.collision_size * SHRINK_SCALE,
+        None => config.player.collision_size,
+    }
+}
+
+pub struct PowerUpPlugin;
+
+impl Plugin for PowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WorldSpeed>()
+            .add_systems(OnEnter(AppState::InGame), setup_power_ups)
+            .add_systems(
+                Update,
+                (
+                    spawn_power_ups.run_if(not(level_active)),
+                    collect_power_ups,
+                    attract_collectibles,
+                    tick_effect::<Shield>,
+                    tick_effect::<SlowMotion>,
+                    tick_effect::<Shrink>,
+                    tick_effect::<Magnet>,
+                    tick_effect::<HitGrace>,
+                    update_world_speed,
+                    update_power_up_hud,
+                )
+                    .run_if(in_state(PlayPhase::Playing)),
+            )
+            .add_systems(
+                Update,
+                apply_effect_visuals.run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(OnExit(AppState::InGame), cleanup_power_up_hud);
+    }
+}
+
+fn setup_power_ups(mut commands: Commands, mut world_speed: ResMut<WorldSpeed>) {
+    *world_speed = WorldSpeed::default();
+
+    commands.spawn((
+        PowerUpHud,
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.0),
+            left: Val::Px(20.0),
+            ..default()
+        },
+        Text::default(),
+        TextFont {
+            font_size: 18.0,
+            ..default()
+        },
+        TextShadow::default(),
+    ));
+}
+
+fn cleanup_power_up_hud(mut commands: Commands, query: Query<Entity, With<PowerUpHud>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+// Places a power-up in the gap of some newly generated pipes
+pub fn spawn_power_ups(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut course: ResMut<Course>,
+    pipe_pair_query: Query<Entity, Added<PipePair>>,
+) {
+    for pipe_pair in &pipe_pair_query {
+        if !course.pickup_rng.random_bool(POWER_UP_CHANCE) {
+            continue;
+        }
+        let power_up = PowerUp::ALL[course.pickup_rng.random_range(0..PowerUp::ALL.len())];
+
+        commands.entity(pipe_pair).with_child((
+            power_up,
+            Collectible,
+            Sprite {
+                image: asset_server.load(power_up.sprite_path()),
+                custom_size: Some(POWER_UP_SIZE),
+                ..default()
+            },
+            Transform::from_xyz(0., 0., 1.),
+        ));
+    }
+}
+
+fn collect_power_ups(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    bird_query: ActiveBirds<(Entity, &GlobalTransform, Option<&Shrink>)>,
+    power_up_query: Query<(Entity, &GlobalTransform, &PowerUp)>,
+    mut audio_events: EventWriter<AudioEvent>,
+) {
+    for (power_up_entity, power_up_transform, power_up) in &power_up_query {
+        let collector = bird_query.iter().find(|(_, bird_transform, shrink)| {
+            check_collision(
+                bird_transform,
+                bird_collision_size(&config, *shrink),
+                power_up_transform,
+                POWER_UP_SIZE,
+            )
+        });
+
+        if let Some((bird, _, _)) = collector {
+            power_up.grant(&mut commands, bird);
+            commands.entity(power_up_entity).despawn();
+            audio_events.write(AudioEvent::new(
+                Sound::PowerUp,
+                power_up_transform.translation().truncate(),
+            ));
+        }
+    }
+}
+
+fn attract_collectibles(
+    time: Res<Time>,
+    magnet_query: ActiveBirds<&GlobalTransform, With<Magnet>>,
+    mut collectible_query: Query<(&GlobalTransform, &mut Transform), With<Collectible>>,
+) {
+    for (global_transform, mut transform) in &mut collectible_query {
+        let position = global_transform.translation().truncate();
+        let closest = magnet_query
+            .iter()
+            .map(|bird| bird.translation().truncate() - position)
+            .filter(|offset| offset.length() < MAGNET_RADIUS)
+            .min_by(|a, b| a.length().total_cmp(&b.length()));
+
+        if let Some(offset) = closest {
+            let step = offset.normalize_or_zero() * MAGNET_PULL_SPEED * time.delta_secs();
+            transform.translation += step.clamp_length_max(offset.length()).extend(0.);
+        }
+    }
+}
+
+fn tick_effect<T: Effect>(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut T)>,
+) {
+    for (entity, mut effect) in &mut query {
+        if effect.timer_mut().tick(time.delta()).finished() {
+            commands.entity(entity).remove::<T>();
+        }
+    }
+}
+
+fn update_world_speed(
+    mut world_speed: ResMut<WorldSpeed>,
+    slow_motion_query: ActiveBirds<(), With<SlowMotion>>,
+) {
+    let speed = if slow_motion_query.is_empty() {
+        1.0
+    } else {
+        SLOW_MOTION_WORLD_SPEED
+    };
+    world_speed.set_if_neq(WorldSpeed(speed));
+}
+
+#[allow(clippy::type_complexity)]
+fn apply_effect_visuals(
+    mut bird_query: Query<
+        (
+            &mut Transform,
+            &mut Visibility,
+            Option<&Shrink>,
+            Option<&HitGrace>,
+        ),
+        With<Player>,
+    >,
+) {
+    for (mut transform, mut visibility, shrink, grace) in &mut bird_query {
+        let scale = if shrink.is_some() { SHRINK_SCALE } else { 1.0 };
+        transform.scale = Vec3::new(scale, scale, 1.0);
+
+        // Blink while passing through pipes after a shield hit
+        let hidden = grace.is_some_and(|grace| (grace.0.elapsed_secs() * 10.0) as u32 % 2 == 1);
+        visibility.set_if_neq(if hidden {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        });
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn update_power_up_hud(
+    bird_query: ActiveBirds<(
+        &PlayerSlot,
+        Option<&Shield>,
+        Option<&SlowMotion>,
+        Option<&Shrink>,
+        Option<&Magnet>,
+    )>,
+    player_count: Res<PlayerCount>,
+    settings: Res<UserSettings>,
+    hud_query: Single<&mut Text, With<PowerUpHud>>,
+) {
+    fn describe<T: Effect>(effect: Option<&T>, language: Language) -> Option<String> {
+        effect.map(|effect| {
+            format!(
+                "{} {:.0}s",
+                language.translate(T::NAME),
+                effect.timer().remaining_secs().ceil()
+            )
+        })
+    }
+
+    let language = settings.language;
+    let mut lines = Vec::new();
+    for (slot, shield, slow_motion, shrink, magnet) in &bird_query {
+        let mut effects: Vec<String> = [
+            describe(slow_motion, language),
+            describe(shrink, language),
+            describe(magnet, language),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if let Some(shield) = shield {
+            effects.insert(
+                0,
+                format!(
+                    "{} x{} {:.0}s",
+                    language.translate(Shield::NAME),
+                    shield.charges,
+                    shield.timer.remaining_secs().ceil()
+                ),
+            );
+        }
+
+        if effects.is_empty() {
+            continue;
+        }
+        let effects = effects.join("  ");
+        lines.push(if player_count.0 > 1 {
+            format!("P{} {}", slot.0 + 1, effects)
+        } else {
+            effects
+        });
+    }
+
+    let mut text = hud_query.into_inner();
+    let label = lines.join("\n");
+    if text.0 != label {
+        text.0 = label;
+    }
+}
@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+
+use crate::game::{components::*, constants::*, score::score_prefix};
+use crate::locale::Phrase;
+use crate::settings::UserSettings;
+
+// Offset from the gap centre, as a fraction of half the gap
+const PERFECT_OFFSET: f32 = 0.15;
+const GOOD_OFFSET: f32 = 0.4;
+// A pass this far out or further only just cleared the pipe
+pub const NEAR_MISS_OFFSET: f32 = 0.6;
+const MAX_MULTIPLIER: u32 = 5;
+
+const POPUP_DURATION: f32 = 0.8;
+const POPUP_RISE_SPEED: f32 = 50.0;
+const POPUP_Z: f32 = 20.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PassGrade {
+    Perfect,
+    Good,
+    Sloppy,
+}
+
+pub fn gap_offset(bird_y: f32, gap_y: f32, gap_size: f32) -> f32 {
+    (bird_y - gap_y).abs() / (gap_size / 2.0).max(1.0)
+}
+
+impl PassGrade {
+    pub fn measure(bird_y: f32, gap_y: f32, gap_size: f32) -> Self {
+        let offset = gap_offset(bird_y, gap_y, gap_size);
+        if offset <= PERFECT_OFFSET {
+            Self::Perfect
+        } else if offset <= GOOD_OFFSET {
+            Self::Good
+        } else {
+            Self::Sloppy
+        }
+    }
+
+    pub fn points(&self) -> u32 {
+        match self {
+            Self::Perfect => 3,
+            Self::Good => 2,
+            Self::Sloppy => 1,
+        }
+    }
+
+    pub fn label(&self) -> Option<Phrase> {
+        match self {
+            Self::Perfect => Some(Phrase::Perfect),
+            Self::Good => Some(Phrase::Good),
+            Self::Sloppy => None,
+        }
+    }
+}
+
+// Multiplier built up by perfect passes in a row
+#[derive(Component, Debug)]
+pub struct Combo {
+    pub multiplier: u32,
+}
+
+impl Default for Combo {
+    fn default() -> Self {
+        Self { multiplier: 1 }
+    }
+}
+
+impl Combo {
+    // Points for a pass; a sloppy pass drops the combo before it counts
+    pub fn award(&mut self, grade: PassGrade) -> u32 {
+        if grade == PassGrade::Sloppy {
+            self.multiplier = 1;
+        }
+        let points = grade.points() * self.multiplier;
+        if grade == PassGrade::Perfect {
+            self.multiplier = (self.multiplier + 1).min(MAX_MULTIPLIER);
+        }
+        points
+    }
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ScorePopup {
+    pub position: Vec2,
+    pub points: u32,
+    pub grade: PassGrade,
+}
+
+#[derive(Component)]
+pub struct FloatingText {
+    pub timer: Timer,
+}
+
+#[derive(Component)]
+pub struct ComboHud;
+
+#[derive(Component)]
+pub struct ComboText;
+
+pub struct PrecisionPlugin;
+
+impl Plugin for PrecisionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ScorePopup>()
+            .add_systems(
+                OnEnter(AppState::InGame),
+                setup_combo_hud.run_if(precision_enabled),
+            )
+            .add_systems(
+                Update,
+                (spawn_popups, animate_popups, update_combo_hud)
+                    .run_if(in_state(PlayPhase::Playing)),
+            )
+            .add_systems(OnExit(AppState::InGame), cleanup_combo_hud);
+    }
+}
+
+pub fn precision_enabled(settings: Res<UserSettings>) -> bool {
+    settings.precision_scoring
+}
+
+fn setup_combo_hud(mut commands: Commands, player_count: Res<PlayerCount>) {
+    let players = player_count.0.clamp(1, MAX_PLAYERS);
+    for (slot, color) in PLAYER_COLORS.into_iter().enumerate().take(players) {
+        commands.spawn((
+            ComboHud,
+            Node {
+                position_type: PositionType::Absolute,
+                // Just under the score
+                top: Val::Px(if players > 1 { 60.0 } else { 75.0 }),
+                left: Val::Px(30.0 + slot as f32 * 135.0),
+                ..default()
+            },
+            children![(
+                ComboText,
+                PlayerSlot(slot),
+                Text::default(),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(color),
+                TextShadow::default(),
+            )],
+        ));
+    }
+}
+
+fn update_combo_hud(
+    player_query: Query<(&PlayerSlot, &Combo), Changed<Combo>>,
+    player_count: Res<PlayerCount>,
+    mut text_query: Query<(&mut Text, &PlayerSlot), With<ComboText>>,
+) {
+    for (slot, combo) in &player_query {
+        for (mut text, _) in text_query
+            .iter_mut()
+            .filter(|(_, text_slot)| *text_slot == slot)
+        {
+            // Hidden until a combo is running
+            text.0 = if combo.multiplier > 1 {
+                format!(
+                    "{}x{}",
+                    score_prefix(*slot, player_count.0),
+                    combo.multiplier
+                )
+            } else {
+                String::new()
+            };
+        }
+    }
+}
+
+fn cleanup_combo_hud(mut commands: Commands, query: Query<Entity, With<ComboHud>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+// Popups live in the game world so they scroll away with it
+fn spawn_popups(
+    mut commands: Commands,
+    mut popups: EventReader<ScorePopup>,
+    settings: Res<UserSettings>,
+    world_query: Single<Entity, With<GameWorld>>,
+) {
+    for popup in popups.read() {
+        let text = match popup.grade.label() {
+            Some(label) => format!("+{} {}", popup.points, settings.language.translate(label)),
+            None => format!("+{}", popup.points),
+        };
+        let color = match popup.grade {
+            PassGrade::Perfect => NEW_BEST_COLOR,
+            _ => Color::WHITE,
+        };
+
+        commands.entity(*world_query).with_child((
+            FloatingText {
+                timer: Timer::from_seconds(POPUP_DURATION, TimerMode::Once),
+            },
+            Text2d(text),
+            TextFont {
+                font_size: 18.0,
+                ..default()
+            },
+            TextColor(color),
+            Transform::from_translation(popup.position.extend(POPUP_Z) + Vec3::Y * 30.0),
+        ));
+    }
+}
+
+fn animate_popups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut popup_query: Query<(Entity, &mut FloatingText, &mut Transform, &mut TextColor)>,
+) {
+    for (entity, mut popup, mut transform, mut color) in &mut popup_query {
+        if popup.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation.y += POPUP_RISE_SPEED * time.delta_secs();
+        color.0.set_alpha(1.0 - popup.timer.fraction());
+    }
+}
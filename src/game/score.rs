@@ -2,34 +2,108 @@ use bevy::prelude::*;
 use crate::game::{
     constants::*,
     components::*,
-    events::AudioEvent,
+    events::{AudioEvent, GameplayEvent, Sound},
+    precision::{Combo, NEAR_MISS_OFFSET, PassGrade, ScorePopup, gap_offset},
 };
+use crate::settings::UserSettings;
+use crate::ui::digits::DigitScore;
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn update_score(
-    player_query: Single<&Transform, With<Player>>,
+    mut player_query: Query<
+        (
+            &Transform,
+            &PlayerSlot,
+            &mut PlayerScore,
+            &mut Combo,
+            Has<Eliminated>,
+        ),
+        With<Player>,
+    >,
     mut pipe_pairs_query: Query<(&Transform, &mut PipePair)>,
     mut score: ResMut<Score>,
+    mut pipes_passed: ResMut<PipesPassed>,
     mut difficulty: ResMut<Difficulty>,
-    score_text_query: Single<&mut Text, With<ScoreText>>,
+    mut score_text_query: Query<(&mut DigitScore, &PlayerSlot), With<ScoreText>>,
     mut audio_events: EventWriter<AudioEvent>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
+    mut popups: EventWriter<ScorePopup>,
+    settings: Res<UserSettings>,
 ) {
-    let player = player_query.into_inner();
-    let mut score_text = score_text_query.into_inner();
+    let mut scored_at = None;
 
     for (transform, mut pipe_pair) in &mut pipe_pairs_query {
         let threshold = transform.translation.x + PIPE_WIDTH / 2.0;
+        if pipe_pair.scored {
+            continue;
+        }
 
-        // Check if player has passed the pipe and we haven't scored it yet
-        if player.translation.x > threshold && !pipe_pair.scored {
-            pipe_pair.scored = true;
-            score.0 += 1;
-            *score_text = Text(score.0.to_string());
-            
-            // Update difficulty based on new score
-            difficulty.update_difficulty(score.0);
-            
-            // Send point sound event
-            audio_events.write(AudioEvent::Point);
+        // Every bird still in the round that has passed the pipe scores it
+        for (player, slot, mut player_score, mut combo, eliminated) in &mut player_query {
+            if !eliminated && player.translation.x > threshold {
+                pipe_pair.scored = true;
+                player_score.0 += if settings.precision_scoring {
+                    // The pair sits at the gap centre
+                    let grade = PassGrade::measure(
+                        player.translation.y,
+                        transform.translation.y,
+                        pipe_pair.gap_size,
+                    );
+                    let points = combo.award(grade);
+                    popups.write(ScorePopup {
+                        position: player.translation.truncate(),
+                        points,
+                        grade,
+                    });
+                    points
+                } else {
+                    1
+                };
+                gameplay_events.write(GameplayEvent::PipePassed { slot: slot.0 });
+                let offset = gap_offset(
+                    player.translation.y,
+                    transform.translation.y,
+                    pipe_pair.gap_size,
+                );
+                if offset >= NEAR_MISS_OFFSET {
+                    gameplay_events.write(GameplayEvent::NearMiss { slot: slot.0 });
+                }
+                scored_at = Some(transform.translation.truncate());
+            }
+        }
+        if pipe_pair.scored {
+            pipes_passed.0 += 1;
         }
     }
-} 
\ No newline at end of file
+
+    let Some(pipe_position) = scored_at else {
+        return;
+    };
+
+    for (_, slot, player_score, _, _) in &player_query {
+        // The shared score follows the leading bird
+        score.0 = score.0.max(player_score.0);
+
+        for (mut score_text, _) in score_text_query
+            .iter_mut()
+            .filter(|(_, text_slot)| *text_slot == slot)
+        {
+            score_text.value = player_score.0;
+        }
+    }
+
+    // Update difficulty based on the pipes passed, whatever the scoring mode
+    difficulty.update_difficulty(pipes_passed.0);
+
+    // Send point sound event
+    audio_events.write(AudioEvent::new(Sound::Point, pipe_position));
+}
+
+// Player tag shown in front of each score in local multiplayer
+pub fn score_prefix(slot: PlayerSlot, players: usize) -> String {
+    if players > 1 {
+        format!("P{} ", slot.0 + 1)
+    } else {
+        String::new()
+    }
+}
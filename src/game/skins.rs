@@ -0,0 +1,138 @@
+use std::fs;
+
+use bevy::{asset::io::file::FileAssetReader, prelude::*};
+use serde::Deserialize;
+
+use crate::game::{animation::FlapTiming, components::BirdTextures};
+
+pub const DEFAULT_SKIN: &str = "yellow";
+pub const SKINS_DIR: &str = "skins";
+
+const FRAME_NAMES: [&str; 3] = ["upflap", "midflap", "downflap"];
+const MANIFEST_NAME: &str = "skin.ron";
+
+#[derive(Clone, Debug)]
+pub struct Skin {
+    pub id: String,
+    pub name: String,
+    // Asset paths of the up, mid and down flap frames
+    pub frames: [String; 3],
+    pub timing: FlapTiming,
+}
+
+// Optional `skin.ron` next to the frames of an extra skin
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct SkinManifest {
+    name: Option<String>,
+    timing: FlapTiming,
+}
+
+impl Skin {
+    fn built_in(id: &str, name: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            frames: FRAME_NAMES.map(|frame| format!("sprites/{id}bird-{frame}.png")),
+            timing: FlapTiming::default(),
+        }
+    }
+
+    pub fn load_textures(&self, asset_server: &AssetServer) -> BirdTextures {
+        let [up, mid, down] = &self.frames;
+        BirdTextures {
+            up: asset_server.load(up),
+            mid: asset_server.load(mid),
+            down: asset_server.load(down),
+        }
+    }
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct SkinRegistry {
+    pub skins: Vec<Skin>,
+}
+
+impl Default for SkinRegistry {
+    fn default() -> Self {
+        let mut skins = vec![
+            Skin::built_in("yellow", "Yellow"),
+            Skin::built_in("red", "Red"),
+            Skin::built_in("blue", "Blue"),
+        ];
+        for skin in discover_skins() {
+            if !skins.iter().any(|existing| existing.id == skin.id) {
+                skins.push(skin);
+            }
+        }
+        Self { skins }
+    }
+}
+
+impl SkinRegistry {
+    pub fn get(&self, id: &str) -> &Skin {
+        self.skins
+            .iter()
+            .find(|skin| skin.id == id)
+            .unwrap_or(&self.skins[0])
+    }
+
+    pub fn index_of(&self, id: &str) -> usize {
+        self.skins
+            .iter()
+            .position(|skin| skin.id == id)
+            .unwrap_or(0)
+    }
+
+    pub fn cycle(&self, id: &str, step: isize) -> &Skin {
+        let len = self.skins.len() as isize;
+        let index = (self.index_of(id) as isize + step).rem_euclid(len);
+        &self.skins[index as usize]
+    }
+}
+
+// Extra skins live in `assets/skins/<name>/` with upflap, midflap and downflap images
+// and an optional manifest for the display name and frame timing
+fn discover_skins() -> Vec<Skin> {
+    let skins_path = FileAssetReader::get_base_path()
+        .join("assets")
+        .join(SKINS_DIR);
+    let Ok(entries) = fs::read_dir(&skins_path) else {
+        return Vec::new();
+    };
+
+    let mut skins: Vec<Skin> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let id = entry.file_name().to_str()?.to_string();
+            let frames = FRAME_NAMES.map(|frame| format!("{SKINS_DIR}/{id}/{frame}.png"));
+            let complete = FRAME_NAMES
+                .iter()
+                .all(|frame| entry.path().join(format!("{frame}.png")).is_file());
+            if !complete {
+                warn!("Skipping skin '{id}': expected upflap, midflap and downflap images");
+                return None;
+            }
+
+            let manifest_path = entry.path().join(MANIFEST_NAME);
+            let manifest: SkinManifest = match fs::read_to_string(&manifest_path) {
+                Ok(contents) => ron::from_str(&contents).unwrap_or_else(|err| {
+                    warn!("Invalid {}: {err}", manifest_path.display());
+                    SkinManifest::default()
+                }),
+                Err(_) => SkinManifest::default(),
+            };
+
+            Some(Skin {
+                name: manifest.name.unwrap_or_else(|| id.replace(['_', '-'], " ")),
+                id,
+                frames,
+                timing: manifest.timing,
+            })
+        })
+        .collect();
+
+    skins.sort_by(|a, b| a.id.cmp(&b.id));
+    skins
+}
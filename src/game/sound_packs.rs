@@ -0,0 +1,232 @@
+use std::{collections::HashMap, fs};
+
+use bevy::{asset::io::file::FileAssetReader, prelude::*};
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::game::{
+    components::GameSounds,
+    config::{AudioConfig, GameConfig},
+    events::Sound,
+};
+use crate::settings::UserSettings;
+
+pub const DEFAULT_SOUND_PACK: &str = "classic";
+pub const SOUND_PACKS_DIR: &str = "sound_packs";
+
+const MANIFEST_NAME: &str = "sounds.ron";
+
+// How one game sound is played: a random pick among the files, with the volume and
+// pitch nudged by up to their jitter each time
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct SoundSpec {
+    pub files: Vec<String>,
+    pub volume: f32,
+    pub pitch: f32,
+    pub volume_jitter: f32,
+    pub pitch_jitter: f32,
+}
+
+impl Default for SoundSpec {
+    fn default() -> Self {
+        Self {
+            files: Vec::new(),
+            volume: 1.0,
+            pitch: 1.0,
+            volume_jitter: 0.0,
+            pitch_jitter: 0.0,
+        }
+    }
+}
+
+impl SoundSpec {
+    fn file(path: &str) -> Self {
+        Self {
+            files: vec![path.to_string()],
+            ..default()
+        }
+    }
+
+    // Sounds of the built-in pack, as set in the game config
+    pub fn built_in(config: &AudioConfig, sound: Sound) -> Self {
+        match sound {
+            Sound::Wing => Self::file(&config.wing_sound),
+            Sound::Point => Self::file(&config.point_sound),
+            Sound::Hit => Self::file(&config.hit_sound),
+            Sound::Die => Self::file(&config.die_sound),
+            Sound::PowerUp => Self::file(&config.swoosh_sound),
+            // A higher pitched point sound, so coins don't sound like scoring
+            Sound::Coin => Self {
+                pitch: 1.5,
+                ..Self::file(&config.point_sound)
+            },
+        }
+    }
+}
+
+// `sounds.ron` in the folder of a sound pack. File names are relative to the folder.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct SoundPackManifest {
+    name: Option<String>,
+    sounds: HashMap<Sound, SoundSpec>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SoundPack {
+    pub id: String,
+    pub name: String,
+    // Sounds the pack replaces, with asset paths; the rest come from the built-in pack
+    pub sounds: HashMap<Sound, SoundSpec>,
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct SoundPackRegistry {
+    pub packs: Vec<SoundPack>,
+}
+
+impl Default for SoundPackRegistry {
+    fn default() -> Self {
+        let mut packs = vec![SoundPack {
+            id: DEFAULT_SOUND_PACK.to_string(),
+            name: "Classic".to_string(),
+            sounds: HashMap::new(),
+        }];
+        for pack in discover_sound_packs() {
+            if !packs.iter().any(|existing| existing.id == pack.id) {
+                packs.push(pack);
+            }
+        }
+        Self { packs }
+    }
+}
+
+impl SoundPackRegistry {
+    pub fn get(&self, id: &str) -> &SoundPack {
+        self.packs
+            .iter()
+            .find(|pack| pack.id == id)
+            .unwrap_or(&self.packs[0])
+    }
+
+    pub fn sound(&self, id: &str, sound: Sound, config: &AudioConfig) -> SoundSpec {
+        self.get(id)
+            .sounds
+            .get(&sound)
+            .cloned()
+            .unwrap_or_else(|| SoundSpec::built_in(config, sound))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LoadedSound {
+    pub variants: Vec<Handle<AudioSource>>,
+    pub spec: SoundSpec,
+}
+
+impl LoadedSound {
+    // A random variant with its volume factor and speed for one playback
+    pub fn pick(&self) -> Option<(Handle<AudioSource>, f32, f32)> {
+        if self.variants.is_empty() {
+            return None;
+        }
+
+        let mut rng = rand::rng();
+        let handle = self.variants[rng.random_range(0..self.variants.len())].clone();
+        let volume = (self.spec.volume + jitter(&mut rng, self.spec.volume_jitter)).max(0.0);
+        let pitch = (self.spec.pitch + jitter(&mut rng, self.spec.pitch_jitter)).max(0.1);
+        Some((handle, volume, pitch))
+    }
+}
+
+pub fn load_sounds(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    config: Res<GameConfig>,
+    registry: Res<SoundPackRegistry>,
+    settings: Res<UserSettings>,
+) {
+    let sounds = Sound::ALL
+        .into_iter()
+        .map(|sound| {
+            let spec = registry.sound(&settings.sound_pack, sound, &config.audio);
+            let variants = spec
+                .files
+                .iter()
+                .map(|file| asset_server.load(file))
+                .collect();
+            (sound, LoadedSound { variants, spec })
+        })
+        .collect();
+    commands.insert_resource(GameSounds { sounds });
+}
+
+fn jitter(rng: &mut impl Rng, amount: f32) -> f32 {
+    if amount > 0.0 {
+        rng.random_range(-amount..=amount)
+    } else {
+        0.0
+    }
+}
+
+// Extra packs live in `assets/sound_packs/<name>/` with a manifest mapping game sounds
+// to one or more files in the folder
+fn discover_sound_packs() -> Vec<SoundPack> {
+    let packs_path = FileAssetReader::get_base_path()
+        .join("assets")
+        .join(SOUND_PACKS_DIR);
+    let Ok(entries) = fs::read_dir(&packs_path) else {
+        return Vec::new();
+    };
+
+    let mut packs: Vec<SoundPack> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let id = entry.file_name().to_str()?.to_string();
+            let manifest_path = entry.path().join(MANIFEST_NAME);
+            let Ok(contents) = fs::read_to_string(&manifest_path) else {
+                warn!("Skipping sound pack '{id}': no {MANIFEST_NAME}");
+                return None;
+            };
+            let manifest: SoundPackManifest = match ron::from_str(&contents) {
+                Ok(manifest) => manifest,
+                Err(err) => {
+                    warn!("Invalid {}: {err}", manifest_path.display());
+                    return None;
+                }
+            };
+
+            let sounds = manifest
+                .sounds
+                .into_iter()
+                .filter_map(|(sound, mut spec)| {
+                    spec.files.retain(|file| {
+                        let found = entry.path().join(file).is_file();
+                        if !found {
+                            warn!("Sound pack '{id}' is missing {file}");
+                        }
+                        found
+                    });
+                    if spec.files.is_empty() {
+                        return None;
+                    }
+                    for file in &mut spec.files {
+                        *file = format!("{SOUND_PACKS_DIR}/{id}/{file}");
+                    }
+                    Some((sound, spec))
+                })
+                .collect();
+
+            Some(SoundPack {
+                name: manifest.name.unwrap_or_else(|| id.replace(['_', '-'], " ")),
+                id,
+                sounds,
+            })
+        })
+        .collect();
+
+    packs.sort_by(|a, b| a.id.cmp(&b.id));
+    packs
+}
@@ -1,14 +1,41 @@
 use bevy::prelude::*;
+use crate::profile::{ActiveProfile, Profile};
+use crate::settings::UserSettings;
 use crate::game::{
     constants::*,
     components::*,
-    events::AudioEvent,
+    config::GameConfig,
+    course::{Course, CourseSelection, CourseSettings},
+    skins::SkinRegistry,
+    animation::{BirdAtlas, FlapAnimation, FlapTiming, animate_flap, apply_flap_frame, build_bird_atlas},
+    events::{AudioEvent, DeathCause, GameplayEvent, Sound},
+    level::{ActiveLevel, CourseProgress, level_active},
     collision::check_collision,
-    audio::{play_audio_events, play_background_music, stop_background_music},
-    player::{apply_gravity, handle_jump_input, detect_gameover},
-    pipes::{generate_pipes, move_pipes, destroy_pipes},
+    audio::{apply_audio_levels, play_audio_events},
+    player::{
+        apply_gravity, detect_gameover, end_round, handle_jump_input, handle_pause_input,
+        hover_player,
+    },
+    pipes::{
+        generate_pipes, spawn_level_pipes, finish_level, move_pipes, animate_pipes, destroy_pipes,
+    },
     score::update_score,
-    ui::{setup_ui, setup_gameover, handle_gameover_menu_button},
+    ui::{
+        setup_ui, setup_get_ready, cleanup_get_ready, setup_pause, cleanup_pause, setup_gameover,
+        handle_gameover_menu_button,
+    },
+    coins::CoinPlugin,
+    ghost::{
+        ActiveGhost, LaunchedReplay, Replay, RunRecorder, cleanup_ghost_hud, move_ghost,
+        record_run, save_run, spawn_ghost, update_ghost_hud,
+    },
+    highscore::{HighScore, update_high_score},
+    medals::MedalPlugin,
+    music::MusicPlugin,
+    powerups::{HitGrace, PowerUpPlugin, Shield, Shrink, WorldSpeed, bird_collision_size, grant},
+    precision::{Combo, PrecisionPlugin},
+    sound_packs::load_sounds,
+    trails::TrailPlugin,
 };
 
 pub struct GamePlugin;
@@ -17,27 +44,78 @@ impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(PipeInterval::default())
             .insert_resource(Score::default())
+            .insert_resource(PipesPassed::default())
             .insert_resource(Difficulty::default())
+            .insert_resource(GameConfig::default())
+            .init_resource::<CourseSelection>()
+            .init_resource::<ActiveLevel>()
+            .init_resource::<CourseProgress>()
+            .init_resource::<PlayerCount>()
+            .init_resource::<ActiveGhost>()
+            .init_resource::<LaunchedReplay>()
+            .init_resource::<RunRecorder>()
+            .init_resource::<HighScore>()
             .add_event::<AudioEvent>()
-            .add_systems(OnEnter(AppState::InGame), (setup, setup_ui, play_background_music))
+            .add_event::<GameplayEvent>()
+            .add_sub_state::<PlayPhase>()
+            .add_plugins((
+                PowerUpPlugin,
+                CoinPlugin,
+                TrailPlugin,
+                MedalPlugin,
+                PrecisionPlugin,
+                MusicPlugin,
+            ))
+            .add_systems(
+                OnEnter(AppState::InGame),
+                (setup, setup_ui, load_sounds, spawn_ghost.after(setup)),
+            )
+            .add_systems(Update, apply_audio_levels)
+            .add_systems(OnEnter(PlayPhase::GetReady), setup_get_ready)
+            .add_systems(OnExit(PlayPhase::GetReady), cleanup_get_ready)
+            .add_systems(OnEnter(PlayPhase::Paused), setup_pause)
+            .add_systems(OnExit(PlayPhase::Paused), cleanup_pause)
+            .add_systems(
+                Update,
+                (
+                    (move_bg, handle_jump_input, animate_flap)
+                        .run_if(not(in_state(PlayPhase::Paused))),
+                    handle_pause_input,
+                    hover_player.run_if(in_state(PlayPhase::GetReady)),
+                    build_bird_atlas.run_if(not(resource_exists::<BirdAtlas>)),
+                    apply_flap_frame,
+                    play_audio_events,
+                )
+                    .run_if(in_state(AppState::InGame)),
+            )
             .add_systems(
                 Update,
                 (
-                    move_bg,
                     apply_gravity,
-                    handle_jump_input,
                     detect_collisions,
                     detect_gameover,
-                    generate_pipes,
+                    end_round.after(detect_collisions).after(detect_gameover),
+                    generate_pipes.run_if(not(level_active)),
+                    spawn_level_pipes.run_if(level_active),
                     move_pipes,
+                    animate_pipes,
                     destroy_pipes,
                     update_score,
-                    play_audio_events,
+                    finish_level.after(update_score).run_if(level_active),
                 )
-                    .run_if(in_state(AppState::InGame)),
+                    .run_if(in_state(PlayPhase::Playing)),
+            )
+            .add_systems(
+                Update,
+                (record_run, move_ghost, update_ghost_hud)
+                    .after(update_score)
+                    .run_if(in_state(PlayPhase::Playing)),
+            )
+            .add_systems(OnExit(AppState::InGame), cleanup_ghost_hud)
+            .add_systems(
+                OnEnter(AppState::GameOver),
+                (update_high_score, setup_gameover, save_run).chain(),
             )
-            .add_systems(OnExit(AppState::InGame), stop_background_music)
-            .add_systems(OnEnter(AppState::GameOver), setup_gameover)
             .add_systems(
                 Update,
                 (handle_gameover_menu_button).run_if(in_state(AppState::GameOver)),
@@ -46,52 +124,116 @@ impl Plugin for GamePlugin {
     }
 }
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let bird_textures = BirdTextures {
-        up: asset_server.load("sprites/yellowbird-upflap.png"),
-        mid: asset_server.load("sprites/yellowbird-midflap.png"),
-        down: asset_server.load("sprites/yellowbird-downflap.png"),
-    };
-    let pipe_texture = PipeTextures {
-        green_pipe: asset_server.load("sprites/pipe-green.png"),
-        red_pipe: asset_server.load("sprites/pipe-red.png"),
+#[allow(clippy::too_many_arguments)]
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    active_level: Res<ActiveLevel>,
+    course_selection: Res<CourseSelection>,
+    skins: Res<SkinRegistry>,
+    settings: Res<UserSettings>,
+    player_count: Res<PlayerCount>,
+    profile: Res<Profile>,
+    active_profile: Res<ActiveProfile>,
+    launched_replay: Res<LaunchedReplay>,
+) {
+    // Racing a ghost replays the course it was recorded on
+    let ghost = ActiveGhost::for_run(
+        &settings,
+        &active_profile.0,
+        &launched_replay,
+        &active_level,
+        &course_selection,
+    );
+    let ghost_course = ghost.0.as_ref().and_then(Replay::course_settings);
+    let course_settings = match (ghost_course, &*course_selection) {
+        (Some(settings), _) => settings,
+        (None, CourseSelection::Random) => CourseSettings::random(settings.difficulty),
+        (None, CourseSelection::Fixed(settings)) => settings.clone(),
     };
+    let config = course_settings.config();
 
-    let game_sounds = GameSounds {
-        wing: asset_server.load("audio/wing.ogg"),
-        point: asset_server.load("audio/point.ogg"),
-        hit: asset_server.load("audio/hit.ogg"),
-        die: asset_server.load("audio/die.ogg"),
-    };
+    let skin = skins.get(profile.skin(&settings));
+    let bird_textures = skin.load_textures(&asset_server);
 
-    let bird_image = bird_textures.up.clone();
+    let bird_image = bird_textures.mid.clone();
 
+    // The atlas is rebuilt for the current skin once its frames are loaded
+    commands.remove_resource::<BirdAtlas>();
     commands.insert_resource(bird_textures);
-    commands.insert_resource(pipe_texture);
-    commands.insert_resource(game_sounds);
+    commands.insert_resource(PipeTextures::load(&asset_server));
+    commands.insert_resource(PipeInterval(Timer::from_seconds(
+        config.pipes.spawn_interval,
+        TimerMode::Repeating,
+    )));
+    commands.insert_resource(Difficulty::default());
+    // Only solo runs on generated courses count towards the personal best
+    commands.insert_resource(RunRecorder {
+        replay: Replay {
+            course: course_settings.code(),
+            ..default()
+        },
+        enabled: active_level.level.is_none() && player_count.0 == 1,
+    });
+    commands.insert_resource(ghost);
+    commands.insert_resource(Course::new(course_settings));
+    commands.insert_resource(config);
+    commands.insert_resource(CourseProgress {
+        distance: active_level.start_distance,
+        next_pipe: 0,
+    });
+
+    spawn_world(
+        &mut commands,
+        &asset_server,
+        bird_image,
+        skin.timing,
+        player_count.0,
+        profile.background_path(),
+    );
+}
 
+pub fn spawn_world(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    bird_image: Handle<Image>,
+    timing: FlapTiming,
+    players: usize,
+    background: &str,
+) -> Entity {
     let root = commands
         .spawn((GameWorld, Transform::default(), Visibility::Visible))
         .id();
 
     commands.entity(root).with_children(|parent| {
-        // Player
-        parent.spawn((
-            Sprite {
-                image: bird_image,
-                ..default()
-            },
-            Transform::from_xyz(-150., 70., Z_POS_PLAYER),
-            Velocity(0.),
-            Player,
-            Collider, // Add collider to player
-        ));
+        // Players, stacked so every bird starts in the clear
+        for (slot, color) in PLAYER_COLORS.into_iter().enumerate().take(players.max(1)) {
+            parent.spawn((
+                Sprite {
+                    image: bird_image.clone(),
+                    color,
+                    ..default()
+                },
+                Transform::from_xyz(
+                    -150.,
+                    70. - slot as f32 * PLAYER_SPAWN_SPACING,
+                    Z_POS_PLAYER + slot as f32 * 0.1,
+                ),
+                Velocity(0.),
+                FlapAnimation::new(timing),
+                Player,
+                PlayerSlot(slot),
+                PlayerScore::default(),
+                Combo::default(),
+                Collider, // Add collider to player
+            ));
+        }
 
         // Background & Platform
         for i in -1..=2 {
             parent.spawn((
                 Sprite {
-                    image: asset_server.load(BG_SPRITE_PATH),
+                    image: asset_server.load(background),
                     custom_size: Some(Vec2::new(BG_IMG_DIMENSIONS.0, BG_IMG_DIMENSIONS.1)),
                     ..default()
                 },
@@ -108,33 +250,85 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
             ));
         }
     });
+
+    root
 }
 
+#[allow(clippy::type_complexity)]
 fn detect_collisions(
-    player_query: Single<&GlobalTransform, (With<Player>, With<Collider>)>,
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut player_query: ActiveBirds<
+        (
+            Entity,
+            &GlobalTransform,
+            &PlayerSlot,
+            Option<&Shrink>,
+            Option<&mut Shield>,
+        ),
+        (With<Collider>, Without<HitGrace>),
+    >,
     pipe_query: Query<&GlobalTransform, (With<Pipe>, With<Collider>)>,
-    mut app_state: ResMut<NextState<AppState>>,
     mut audio_events: EventWriter<AudioEvent>,
+    mut gameplay_events: EventWriter<GameplayEvent>,
 ) {
-    let player_transform = player_query.into_inner();
-
-    for pipe_transform in &pipe_query {
-        if check_collision(player_transform, pipe_transform) {
-            // Send hit sound event
-            audio_events.write(AudioEvent::Hit);
-            app_state.set(AppState::GameOver);
-            return;
+    for (entity, player_transform, slot, shrink, shield) in &mut player_query {
+        let player_size = bird_collision_size(&config, shrink);
+        let Some(pipe_transform) = pipe_query.iter().find(|pipe_transform| {
+            check_collision(
+                player_transform,
+                player_size,
+                pipe_transform,
+                config.pipes.collision_size,
+            )
+        }) else {
+            continue;
+        };
+
+        // A shield takes the hit and lets the bird slip through the pipe
+        if let Some(mut shield) = shield {
+            if shield.absorb() {
+                commands.entity(entity).remove::<Shield>();
+            }
+            grant::<HitGrace>(&mut commands, entity);
+            audio_events.write(AudioEvent::new(
+                Sound::Hit,
+                player_transform.translation().truncate(),
+            ));
+            continue;
         }
+
+        // Send hit sound event, the bird then falls out of the round
+        audio_events.write(AudioEvent::new(
+            Sound::Hit,
+            player_transform.translation().truncate(),
+        ));
+        let cause = if pipe_transform.translation().y > player_transform.translation().y {
+            DeathCause::TopPipe
+        } else {
+            DeathCause::BottomPipe
+        };
+        gameplay_events.write(GameplayEvent::Death {
+            slot: slot.0,
+            cause,
+            position: player_transform.translation().truncate(),
+        });
+        commands.entity(entity).insert(Eliminated {
+            at: time.elapsed_secs(),
+        });
     }
 }
 
 fn move_bg(
+    time: Res<Time>,
+    world_speed: Res<WorldSpeed>,
     mut bg_query: Query<&mut Transform, With<BackgroundImage>>,
     mut platform_query: Query<&mut Transform, (With<PlatformImage>, Without<BackgroundImage>)>,
 ) {
     // Move background
     for mut transform in &mut bg_query {
-        transform.translation.x -= BG_SPEED;
+        transform.translation.x -= BG_SPEED * world_speed.0 * time.delta_secs();
 
         if transform.translation.x < -BG_IMG_DIMENSIONS.0 * 1.5 {
             transform.translation.x = BG_IMG_DIMENSIONS.0 * 1.5;
@@ -143,7 +337,7 @@ fn move_bg(
 
     // Move platform
     for mut transform in &mut platform_query {
-        transform.translation.x -= PLATFORM_SPEED;
+        transform.translation.x -= PLATFORM_SPEED * world_speed.0 * time.delta_secs();
 
         if transform.translation.x < -BG_IMG_DIMENSIONS.0 * 1.5 {
             transform.translation.x = BG_IMG_DIMENSIONS.0 * 1.5;
@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+
+use crate::game::{components::*, config::GameConfig, constants::*, powerups::WorldSpeed};
+use crate::profile::Profile;
+
+const TRAIL_INTERVAL: f32 = 0.03;
+const TRAIL_LIFETIME: f32 = 0.5;
+const TRAIL_SIZE: f32 = 6.0;
+const SPARKLE_COLORS: [Color; 2] = [Color::srgb(1.0, 0.85, 0.3), Color::WHITE];
+
+#[derive(Component)]
+pub struct TrailParticle {
+    pub timer: Timer,
+}
+
+#[derive(Resource)]
+pub struct TrailEmitter {
+    pub timer: Timer,
+    pub emitted: u32,
+}
+
+impl Default for TrailEmitter {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(TRAIL_INTERVAL, TimerMode::Repeating),
+            emitted: 0,
+        }
+    }
+}
+
+pub struct TrailPlugin;
+
+impl Plugin for TrailPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TrailEmitter>().add_systems(
+            Update,
+            (emit_trail, update_trail).run_if(in_state(PlayPhase::Playing)),
+        );
+    }
+}
+
+fn emit_trail(
+    mut commands: Commands,
+    time: Res<Time>,
+    profile: Res<Profile>,
+    mut emitter: ResMut<TrailEmitter>,
+    bird_query: ActiveBirds<&Transform>,
+    root_query: Single<Entity, With<GameWorld>>,
+) {
+    let Some(trail) = profile.trail.as_deref() else {
+        return;
+    };
+    if !emitter.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    emitter.emitted += 1;
+
+    let color = match trail {
+        "rainbow" => Color::hsl((time.elapsed_secs() * 240.0) % 360.0, 0.9, 0.6),
+        _ => SPARKLE_COLORS[emitter.emitted as usize % SPARKLE_COLORS.len()],
+    };
+
+    let root = root_query.into_inner();
+    for transform in &bird_query {
+        let position = transform.translation;
+        commands.entity(root).with_child((
+            TrailParticle {
+                timer: Timer::from_seconds(TRAIL_LIFETIME, TimerMode::Once),
+            },
+            Sprite {
+                color,
+                custom_size: Some(Vec2::splat(TRAIL_SIZE)),
+                ..default()
+            },
+            Transform::from_xyz(position.x - 12., position.y, Z_POS_PLAYER - 0.5),
+        ));
+    }
+}
+
+// Particles drift back with the pipes and fade out
+fn update_trail(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    difficulty: Res<Difficulty>,
+    world_speed: Res<WorldSpeed>,
+    mut particle_query: Query<(Entity, &mut TrailParticle, &mut Transform, &mut Sprite)>,
+) {
+    let speed =
+        config.pipes.speed * difficulty.pipe_speed_multiplier * world_speed.0 * time.delta_secs();
+
+    for (entity, mut particle, mut transform, mut sprite) in &mut particle_query {
+        if particle.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let remaining = particle.timer.fraction_remaining();
+        transform.translation.x -= speed;
+        transform.scale = Vec3::splat(remaining);
+        sprite.color.set_alpha(remaining);
+    }
+}
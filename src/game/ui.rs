@@ -1,12 +1,29 @@
 use bevy::prelude::*;
 use bevy::text::LineHeight;
+use crate::input::{ActionState, GameAction};
+use crate::locale::Phrase;
+use crate::settings::UserSettings;
 use crate::game::{
     constants::*,
     components::*,
+    config::GameConfig,
+    course::Course,
+    highscore::HighScore,
+    level::ActiveLevel,
+    medals::medal_widget,
+    score::score_prefix,
 };
+use crate::ui::digits::{DigitAlign, DigitScore, DigitSize};
 
-pub fn setup_ui(mut commands: Commands, mut score: ResMut<Score>) {
+pub fn setup_ui(
+    mut commands: Commands,
+    mut score: ResMut<Score>,
+    mut pipes_passed: ResMut<PipesPassed>,
+    player_count: Res<PlayerCount>,
+) {
     score.0 = 0;
+    pipes_passed.0 = 0;
+    let players = player_count.0.clamp(1, MAX_PLAYERS);
     let root = commands
         .spawn((
             GameUi,
@@ -19,103 +36,254 @@ pub fn setup_ui(mut commands: Commands, mut score: ResMut<Score>) {
         .id();
 
     commands.entity(root).with_children(|parent| {
-        // Score
-        parent.spawn((
-            ScoreText,
-            Node {
-                position_type: PositionType::Absolute,
-                top: Val::Px(10.0),
-                left: Val::Px(30.0),
-                ..default()
-            },
-            Text(score.0.to_string()),
-            TextFont {
-                font_size: 50.0,
-                line_height: LineHeight::RelativeToFont(2.0),
-                ..default()
-            },
-            TextColor(Color::WHITE),
-            TextShadow {
-                offset: Vec2::splat(0.8),
-                color: Color::BLACK,
-            },
-        ));
+        // Score, one per player side by side
+        for (slot, color) in PLAYER_COLORS.into_iter().enumerate().take(players) {
+            parent.spawn((
+                ScoreText,
+                PlayerSlot(slot),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(30.0),
+                    left: Val::Px(30.0 + slot as f32 * 135.0),
+                    ..default()
+                },
+                DigitScore::new(
+                    score.0,
+                    if players > 1 {
+                        DigitSize::Small
+                    } else {
+                        DigitSize::Large
+                    },
+                )
+                .with_color(color)
+                .with_prefix(score_prefix(PlayerSlot(slot), players)),
+            ));
+        }
     });
 }
 
-pub fn setup_gameover(mut commands: Commands) {
+pub fn setup_get_ready(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.spawn((
+        GetReadyLayer,
         Node {
             width: Val::Percent(100.),
             height: Val::Percent(100.),
             justify_content: JustifyContent::Center,
             align_items: AlignItems::Center,
-            flex_direction: FlexDirection::Column,
             ..default()
         },
-        BackgroundColor(Color::srgba(0., 0., 0., 0.8)),
-        GameOverLayer,
-        children![
-            (
-                // Game over label
-                Node {
-                    margin: UiRect::top(Val::Px(100.0)),
-                    ..default()
-                },
-                Text("Game Over".to_string()),
-                TextFont {
-                    font_size: 70.0,
-                    line_height: LineHeight::RelativeToFont(2.0),
-                    ..default()
-                }
-            ),
-            (
-                // Buttons
-                Node {
-                    width: Val::Percent(100.),
-                    height: Val::Percent(100.),
-                    justify_content: JustifyContent::Center,
-                    align_items: AlignItems::Center,
-                    flex_direction: FlexDirection::Row,
-                    ..default()
-                },
-                children![
-                    (
-                        // Main menu
-                        Node {
-                            width: Val::Percent(30.),
-                            height: Val::Percent(20.),
-                            justify_content: JustifyContent::Center,
-                            align_items: AlignItems::Center,
-                            margin: UiRect::all(Val::Px(10.0)),
-                            ..default()
-                        },
-                        Button,
-                        BackgroundColor(BUTTON_COLOR_IDLE),
-                        GameOverMenuButton::MainMenu,
-                        children![Text("Main Menu".to_string())],
-                    ),
-                    (
-                        // Retry
-                        Node {
-                            width: Val::Percent(30.),
-                            height: Val::Percent(20.),
-                            justify_content: JustifyContent::Center,
-                            align_items: AlignItems::Center,
-                            margin: UiRect::all(Val::Px(10.0)),
-                            ..default()
-                        },
-                        Button,
-                        BackgroundColor(BUTTON_COLOR_IDLE),
-                        GameOverMenuButton::Retry,
-                        children![Text("Retry".to_string())],
-                    ),
-                ]
-            )
-        ],
+        children![ImageNode::new(asset_server.load(GET_READY_SPRITE_PATH))],
     ));
 }
 
+pub fn cleanup_get_ready(mut commands: Commands, query: Query<Entity, With<GetReadyLayer>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn setup_pause(mut commands: Commands, settings: Res<UserSettings>) {
+    commands.spawn((
+        PauseLayer,
+        Node {
+            width: Val::Percent(100.),
+            height: Val::Percent(100.),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0., 0., 0., 0.5)),
+        children![(
+            Text(settings.language.translate(Phrase::Paused).to_string()),
+            TextFont {
+                font_size: 50.0,
+                ..default()
+            },
+            TextShadow::default(),
+        )],
+    ));
+}
+
+pub fn cleanup_pause(mut commands: Commands, query: Query<Entity, With<PauseLayer>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn setup_gameover(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    active_level: Res<ActiveLevel>,
+    course: Res<Course>,
+    config: Res<GameConfig>,
+    score: Res<Score>,
+    pipes_passed: Res<PipesPassed>,
+    high_score: Res<HighScore>,
+    settings: Res<UserSettings>,
+    player_query: Query<(&PlayerSlot, &PlayerScore, Option<&Eliminated>), With<Player>>,
+) {
+    let language = settings.language;
+    // Test-play sessions return to the editor instead of the main menu
+    let exit_label = if active_level.playtest {
+        Phrase::Editor
+    } else {
+        Phrase::MainMenu
+    };
+    let course_label = if active_level.level.is_some() {
+        String::new()
+    } else {
+        language.fill(Phrase::CourseCode, &[&course.settings.code()])
+    };
+
+    let (best_label, best_color) = if high_score.new_best {
+        (language.fill(Phrase::NewBest, &[&high_score.best]), NEW_BEST_COLOR)
+    } else {
+        (language.fill(Phrase::Best, &[&high_score.best]), Color::WHITE)
+    };
+
+    // Highest score first, then whoever stayed in the round the longest
+    let mut results: Vec<_> = player_query
+        .iter()
+        .map(|(slot, score, eliminated)| {
+            (*slot, score.0, eliminated.map_or(f32::INFINITY, |e| e.at))
+        })
+        .collect();
+    results.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then(b.2.total_cmp(&a.2))
+            .then(a.0.0.cmp(&b.0.0))
+    });
+
+    let layer = commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0., 0., 0., 0.8)),
+            GameOverLayer,
+            children![
+                (
+                    // Game over label
+                    Node {
+                        margin: UiRect::top(Val::Px(100.0)),
+                        ..default()
+                    },
+                    Text(language.translate(Phrase::GameOver).to_string()),
+                    TextFont {
+                        font_size: 70.0,
+                        line_height: LineHeight::RelativeToFont(2.0),
+                        ..default()
+                    }
+                ),
+                (
+                    // Final score
+                    Node {
+                        margin: UiRect::bottom(Val::Px(6.0)),
+                        ..default()
+                    },
+                    DigitScore::new(score.0, DigitSize::Large).with_align(DigitAlign::Center),
+                ),
+                (
+                    // Personal best, highlighted when this run beat it
+                    Text(best_label),
+                    TextFont {
+                        font_size: 28.0,
+                        ..default()
+                    },
+                    TextColor(best_color),
+                    TextShadow::default(),
+                ),
+                (
+                    // Course code to share with other players
+                    Text(course_label),
+                    TextFont {
+                        font_size: 20.0,
+                        ..default()
+                    },
+                ),
+                (
+                    // Buttons
+                    Node {
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        flex_direction: FlexDirection::Row,
+                        ..default()
+                    },
+                    children![
+                        (
+                            // Main menu
+                            Node {
+                                width: Val::Percent(30.),
+                                height: Val::Percent(20.),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                margin: UiRect::all(Val::Px(10.0)),
+                                ..default()
+                            },
+                            Button,
+                            BackgroundColor(BUTTON_COLOR_IDLE),
+                            GameOverMenuButton::MainMenu,
+                            children![Text(language.translate(exit_label).to_string())],
+                        ),
+                        (
+                            // Retry
+                            Node {
+                                width: Val::Percent(30.),
+                                height: Val::Percent(20.),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                margin: UiRect::all(Val::Px(10.0)),
+                                ..default()
+                            },
+                            Button,
+                            BackgroundColor(BUTTON_COLOR_IDLE),
+                            GameOverMenuButton::Retry,
+                            children![Text(language.translate(Phrase::Retry).to_string())],
+                        ),
+                    ]
+                )
+            ],
+        ))
+        .id();
+
+    // Results screen for local multiplayer
+    if results.len() > 1 {
+        let ranking = commands
+            .spawn(Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            })
+            .with_children(|parent| {
+                for (rank, (slot, score, _)) in results.iter().enumerate() {
+                    let label = language.fill(Phrase::PlayerRank, &[&(rank + 1), &(slot.0 + 1)]);
+                    parent.spawn(
+                        DigitScore::new(*score, DigitSize::Small)
+                            .with_color(PLAYER_COLORS[slot.0])
+                            .with_prefix(format!("{label}  ")),
+                    );
+                }
+            })
+            .id();
+        // Between the course code and the buttons
+        commands.entity(layer).insert_children(4, &[ranking]);
+    }
+
+    // Medal just below the game over label
+    if let Some(medal) = config.medals.medal(pipes_passed.0) {
+        let medal = commands.spawn(medal_widget(&asset_server, medal, language)).id();
+        commands.entity(layer).insert_children(1, &[medal]);
+    }
+}
+
 type QueryButton<'w, 's, 'a> = Query<
     'w,
     's,
@@ -129,14 +297,27 @@ type QueryButton<'w, 's, 'a> = Query<
 
 pub fn handle_gameover_menu_button(
     mut button_query: QueryButton,
+    actions: Res<ActionState>,
+    active_level: Res<ActiveLevel>,
     mut app_state: ResMut<NextState<AppState>>,
 ) {
+    if actions.just_pressed(GameAction::Back) {
+        app_state.set(if active_level.playtest {
+            AppState::Editor
+        } else {
+            AppState::MainMenu
+        });
+    }
+
     for (interaction, button, mut color) in &mut button_query {
         match *interaction {
             Interaction::Pressed => {
                 *color = BackgroundColor(BUTTON_COLOR_PRESSED);
 
                 match button {
+                    GameOverMenuButton::MainMenu if active_level.playtest => {
+                        app_state.set(AppState::Editor);
+                    }
                     GameOverMenuButton::MainMenu => {
                         app_state.set(AppState::MainMenu);
                     }
@@ -0,0 +1,237 @@
+use std::{
+    fs, io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    AppState, PlayerCount, Score,
+    coins::RunCoins,
+    course::{Course, DifficultyPreset},
+    events::DeathCause,
+    level::ActiveLevel,
+};
+use crate::leaderboard::GameMode;
+use crate::profile::{ActiveProfile, Profiles};
+use crate::stats::RunStats;
+use crate::storage;
+
+const HISTORY_FILE: &str = "history.jsonl";
+const EXPORT_COMMAND: &str = "export-history";
+const CSV_HEADER: &str = "timestamp,mode,players,difficulty,seed,level,score,duration,flaps,pipes_passed,coins,death_cause,death_x,death_y";
+
+// One finished run, kept one per line in the history file
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RunRecord {
+    pub timestamp: u64,
+    pub mode: GameMode,
+    pub players: usize,
+    pub difficulty: DifficultyPreset,
+    pub seed: u32,
+    // Name of the hand-made level, if the run wasn't on a generated course
+    pub level: Option<String>,
+    pub score: u32,
+    // Seconds spent flying, pauses excluded
+    pub duration: f64,
+    pub flaps: u64,
+    pub pipes_passed: u64,
+    pub coins: u32,
+    pub death_cause: Option<DeathCause>,
+    pub death_x: Option<f32>,
+    pub death_y: Option<f32>,
+}
+
+impl RunRecord {
+    fn csv_row(&self) -> String {
+        let optional = |value: Option<f32>| value.map(|v| v.to_string()).unwrap_or_default();
+        [
+            self.timestamp.to_string(),
+            self.mode.name().to_string(),
+            self.players.to_string(),
+            self.difficulty.name().to_string(),
+            self.seed.to_string(),
+            csv_field(self.level.as_deref().unwrap_or_default()),
+            self.score.to_string(),
+            format!("{:.2}", self.duration),
+            self.flaps.to_string(),
+            self.pipes_passed.to_string(),
+            self.coins.to_string(),
+            self.death_cause
+                .map(|cause| cause.name().to_string())
+                .unwrap_or_default(),
+            optional(self.death_x),
+            optional(self.death_y),
+        ]
+        .join(",")
+    }
+}
+
+// Quotes a field that would otherwise break the row apart
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn append(profile: &str, record: &RunRecord) -> io::Result<()> {
+    let line = serde_json::to_string(record).map_err(io::Error::other)?;
+    storage::append_line(storage::profile_path(profile, HISTORY_FILE), &line)
+}
+
+// Every readable run of a profile, oldest first. Only the export command reads the
+// history, before any logging is set up, so skipped lines go straight to stderr.
+pub fn load(profile: &str) -> io::Result<Vec<RunRecord>> {
+    let lines = storage::read_lines(storage::profile_path(profile, HISTORY_FILE))?;
+    Ok(lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(index, line)| match serde_json::from_str(line) {
+            Ok(record) => Some(record),
+            Err(err) => {
+                eprintln!("Skipping unreadable run history line {}: {err}", index + 1);
+                None
+            }
+        })
+        .collect())
+}
+
+pub struct HistoryPlugin;
+
+impl Plugin for HistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::GameOver), record_history);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn record_history(
+    run_stats: Res<RunStats>,
+    run_coins: Res<RunCoins>,
+    score: Res<Score>,
+    course: Res<Course>,
+    active_level: Res<ActiveLevel>,
+    player_count: Res<PlayerCount>,
+    active: Res<ActiveProfile>,
+) {
+    // Test-plays from the editor don't count
+    if active_level.playtest {
+        return;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let death = run_stats.last_death;
+    let record = RunRecord {
+        timestamp,
+        mode: GameMode::from_players(player_count.0),
+        players: player_count.0,
+        difficulty: course.settings.preset,
+        seed: course.settings.seed,
+        level: active_level.level.as_ref().map(|level| level.name.clone()),
+        score: score.0,
+        duration: run_stats.time_played,
+        flaps: run_stats.flaps,
+        pipes_passed: run_stats.pipes_passed,
+        coins: run_coins.0,
+        death_cause: death.map(|(cause, _)| cause),
+        death_x: death.map(|(_, position)| position.x),
+        death_y: death.map(|(_, position)| position.y),
+    };
+    if let Err(err) = append(&active.0, &record) {
+        warn!("Failed to save run history: {err}");
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+// `export-history <json|csv> [--profile <name>] [--output <file>]`, or `None` when
+// the game was started normally
+pub fn run_export(args: &[String]) -> Option<AppExit> {
+    if args.first().map(String::as_str) != Some(EXPORT_COMMAND) {
+        return None;
+    }
+
+    match export(&args[1..]) {
+        Ok(()) => Some(AppExit::Success),
+        Err(err) => {
+            eprintln!("{EXPORT_COMMAND}: {err}");
+            eprintln!(
+                "usage: bevy-flappy {EXPORT_COMMAND} <json|csv> [--profile <name>] [--output <file>]"
+            );
+            Some(AppExit::error())
+        }
+    }
+}
+
+fn export(args: &[String]) -> Result<(), String> {
+    let mut format = None;
+    let mut profile = None;
+    let mut output = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "json" => format = Some(ExportFormat::Json),
+            "csv" => format = Some(ExportFormat::Csv),
+            "--profile" => profile = Some(args.next().ok_or("--profile needs a name")?),
+            "--output" => output = Some(PathBuf::from(args.next().ok_or("--output needs a file")?)),
+            other => return Err(format!("unexpected argument `{other}`")),
+        }
+    }
+    let format = format.ok_or("choose json or csv")?;
+
+    // Exporting leaves the data directory as it is, even before the first profile exists
+    let profiles = Profiles::read().unwrap_or_default();
+    let id = match profile {
+        Some(name) => Some(
+            profiles
+                .entries
+                .iter()
+                .find(|entry| entry.name == *name || entry.id == *name)
+                .map(|entry| entry.id.clone())
+                .ok_or(format!("no profile called `{name}`"))?,
+        ),
+        None => profiles
+            .get(&profiles.active)
+            .or(profiles.entries.first())
+            .map(|entry| entry.id.clone()),
+    };
+
+    let records = match id {
+        Some(id) => load(&id).map_err(|err| err.to_string())?,
+        None => Vec::new(),
+    };
+    let contents = match format {
+        ExportFormat::Json => {
+            serde_json::to_string_pretty(&records).map_err(|err| err.to_string())? + "\n"
+        }
+        ExportFormat::Csv => {
+            let mut csv = format!("{CSV_HEADER}\n");
+            for record in &records {
+                csv.push_str(&record.csv_row());
+                csv.push('\n');
+            }
+            csv
+        }
+    };
+
+    match output {
+        Some(path) => fs::write(&path, contents)
+            .map_err(|err| format!("failed to write {}: {err}", path.display())),
+        None => {
+            print!("{contents}");
+            Ok(())
+        }
+    }
+}
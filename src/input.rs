@@ -0,0 +1,354 @@
+use bevy::{
+    input::InputSystem,
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::locale::Phrase;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GameAction {
+    Flap,
+    Flap2,
+    Flap3,
+    Flap4,
+    Pause,
+    Confirm,
+    Back,
+    NavigateUp,
+    NavigateDown,
+    NavigateLeft,
+    NavigateRight,
+}
+
+impl GameAction {
+    pub const ALL: [GameAction; 11] = [
+        Self::Flap,
+        Self::Flap2,
+        Self::Flap3,
+        Self::Flap4,
+        Self::Pause,
+        Self::Confirm,
+        Self::Back,
+        Self::NavigateUp,
+        Self::NavigateDown,
+        Self::NavigateLeft,
+        Self::NavigateRight,
+    ];
+
+    // Flap action of each local player, in player order
+    pub const FLAPS: [GameAction; 4] = [Self::Flap, Self::Flap2, Self::Flap3, Self::Flap4];
+
+    pub fn flap(slot: usize) -> Self {
+        Self::FLAPS[slot]
+    }
+
+    pub fn phrase(&self) -> Phrase {
+        match self {
+            Self::Flap => Phrase::Flap,
+            Self::Flap2 => Phrase::FlapP2,
+            Self::Flap3 => Phrase::FlapP3,
+            Self::Flap4 => Phrase::FlapP4,
+            Self::Pause => Phrase::Pause,
+            Self::Confirm => Phrase::Confirm,
+            Self::Back => Phrase::Back,
+            Self::NavigateUp => Phrase::Up,
+            Self::NavigateDown => Phrase::Down,
+            Self::NavigateLeft => Phrase::Left,
+            Self::NavigateRight => Phrase::Right,
+        }
+    }
+
+    pub fn context(&self) -> ActionContext {
+        match self {
+            Self::Flap | Self::Flap2 | Self::Flap3 | Self::Flap4 | Self::Pause => {
+                ActionContext::Gameplay
+            }
+            _ => ActionContext::Menu,
+        }
+    }
+}
+
+// Actions in the same context can't share a binding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionContext {
+    Gameplay,
+    Menu,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Touch,
+    Gamepad(GamepadButton),
+}
+
+impl Binding {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Key(key) => {
+                let name = format!("{key:?}");
+                name.strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .unwrap_or(&name)
+                    .to_string()
+            }
+            Self::Mouse(button) => format!("Mouse {button:?}"),
+            Self::Touch => "Touch".to_string(),
+            Self::Gamepad(button) => format!("Pad {button:?}"),
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct InputBindings {
+    pub bindings: HashMap<GameAction, Vec<Binding>>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        use Binding::*;
+
+        let bindings = HashMap::from_iter([
+            (
+                GameAction::Flap,
+                vec![
+                    Key(KeyCode::Space),
+                    Mouse(MouseButton::Left),
+                    Touch,
+                    Gamepad(GamepadButton::South),
+                ],
+            ),
+            (GameAction::Flap2, vec![Key(KeyCode::KeyW)]),
+            (GameAction::Flap3, vec![Key(KeyCode::KeyI)]),
+            (GameAction::Flap4, vec![Key(KeyCode::ArrowUp)]),
+            (
+                GameAction::Pause,
+                vec![
+                    Key(KeyCode::Escape),
+                    Key(KeyCode::KeyP),
+                    Gamepad(GamepadButton::Start),
+                ],
+            ),
+            (
+                GameAction::Confirm,
+                vec![
+                    Key(KeyCode::Enter),
+                    Key(KeyCode::Space),
+                    Gamepad(GamepadButton::South),
+                ],
+            ),
+            (
+                GameAction::Back,
+                vec![Key(KeyCode::Escape), Gamepad(GamepadButton::East)],
+            ),
+            (
+                GameAction::NavigateUp,
+                vec![Key(KeyCode::ArrowUp), Gamepad(GamepadButton::DPadUp)],
+            ),
+            (
+                GameAction::NavigateDown,
+                vec![Key(KeyCode::ArrowDown), Gamepad(GamepadButton::DPadDown)],
+            ),
+            (
+                GameAction::NavigateLeft,
+                vec![Key(KeyCode::ArrowLeft), Gamepad(GamepadButton::DPadLeft)],
+            ),
+            (
+                GameAction::NavigateRight,
+                vec![Key(KeyCode::ArrowRight), Gamepad(GamepadButton::DPadRight)],
+            ),
+        ]);
+
+        Self { bindings }
+    }
+}
+
+impl InputBindings {
+    pub fn get(&self, action: GameAction) -> &[Binding] {
+        self.bindings
+            .get(&action)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    // Another action in the same context that already uses `binding`
+    pub fn conflict(&self, action: GameAction, binding: Binding) -> Option<GameAction> {
+        GameAction::ALL.into_iter().find(|other| {
+            *other != action
+                && other.context() == action.context()
+                && self.get(*other).contains(&binding)
+        })
+    }
+
+    pub fn set(&mut self, action: GameAction, slot: usize, binding: Option<Binding>) {
+        let bindings = self.bindings.entry(action).or_default();
+        match binding {
+            Some(binding) if slot < bindings.len() => bindings[slot] = binding,
+            Some(binding) => bindings.push(binding),
+            None if slot < bindings.len() => {
+                bindings.remove(slot);
+            }
+            None => {}
+        }
+    }
+
+    // Fills in actions missing from an older settings file
+    pub fn with_defaults(mut self) -> Self {
+        for (action, bindings) in Self::default().bindings {
+            self.bindings.entry(action).or_insert(bindings);
+        }
+        self
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct ActionState {
+    pressed: HashSet<GameAction>,
+    just_pressed: HashSet<GameAction>,
+    just_released: HashSet<GameAction>,
+    injected: HashSet<GameAction>,
+    held: HashSet<GameAction>,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: GameAction) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: GameAction) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    pub fn just_released(&self, action: GameAction) -> bool {
+        self.just_released.contains(&action)
+    }
+
+    pub fn any_just_pressed(&self, actions: impl IntoIterator<Item = GameAction>) -> bool {
+        actions.into_iter().any(|action| self.just_pressed(action))
+    }
+
+    // Taps an action on the next update, as if a bound button was pressed and released
+    pub fn inject(&mut self, action: GameAction) {
+        self.injected.insert(action);
+    }
+
+    // Keeps an action pressed until `release` is called
+    pub fn hold(&mut self, action: GameAction) {
+        self.held.insert(action);
+    }
+
+    pub fn release(&mut self, action: GameAction) {
+        self.held.remove(&action);
+    }
+
+    pub fn update(&mut self, mut is_pressed: impl FnMut(GameAction) -> bool) {
+        let previous = std::mem::take(&mut self.pressed);
+        let injected = std::mem::take(&mut self.injected);
+
+        for action in GameAction::ALL {
+            if is_pressed(action) || self.held.contains(&action) || injected.contains(&action) {
+                self.pressed.insert(action);
+            }
+        }
+
+        self.just_pressed = self.pressed.difference(&previous).copied().collect();
+        // Injected taps also count when the action was already held down
+        self.just_pressed.extend(injected);
+        self.just_released = previous.difference(&self.pressed).copied().collect();
+    }
+}
+
+pub struct ActionsPlugin;
+
+impl Plugin for ActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputBindings>()
+            .init_resource::<ActionState>()
+            .add_systems(PreUpdate, update_actions.after(InputSystem));
+    }
+}
+
+fn update_actions(
+    bindings: Res<InputBindings>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    gamepads: Query<&Gamepad>,
+    mut actions: ResMut<ActionState>,
+) {
+    actions.update(|action| {
+        bindings.get(action).iter().any(|binding| match binding {
+            Binding::Key(key) => keyboard.pressed(*key),
+            Binding::Mouse(button) => mouse.pressed(*button),
+            Binding::Touch => touches.iter().next().is_some(),
+            Binding::Gamepad(button) => gamepads.iter().any(|gamepad| gamepad.pressed(*button)),
+        })
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nothing_pressed(_: GameAction) -> bool {
+        false
+    }
+
+    #[test]
+    fn injected_tap_is_just_pressed_for_one_update() {
+        let mut actions = ActionState::default();
+        actions.inject(GameAction::Flap);
+
+        actions.update(nothing_pressed);
+        assert!(actions.just_pressed(GameAction::Flap));
+        assert!(actions.pressed(GameAction::Flap));
+        assert!(!actions.just_pressed(GameAction::Pause));
+
+        actions.update(nothing_pressed);
+        assert!(!actions.just_pressed(GameAction::Flap));
+        assert!(!actions.pressed(GameAction::Flap));
+        assert!(actions.just_released(GameAction::Flap));
+    }
+
+    #[test]
+    fn held_action_stays_pressed_until_released() {
+        let mut actions = ActionState::default();
+        actions.hold(GameAction::Confirm);
+
+        actions.update(nothing_pressed);
+        assert!(actions.just_pressed(GameAction::Confirm));
+        assert!(actions.pressed(GameAction::Confirm));
+
+        actions.update(nothing_pressed);
+        assert!(!actions.just_pressed(GameAction::Confirm));
+        assert!(actions.pressed(GameAction::Confirm));
+
+        actions.release(GameAction::Confirm);
+        actions.update(nothing_pressed);
+        assert!(!actions.pressed(GameAction::Confirm));
+        assert!(actions.just_released(GameAction::Confirm));
+    }
+
+    #[test]
+    fn injected_tap_counts_while_held() {
+        let mut actions = ActionState::default();
+        actions.hold(GameAction::Flap);
+        actions.update(nothing_pressed);
+
+        actions.inject(GameAction::Flap);
+        actions.update(nothing_pressed);
+        assert!(actions.just_pressed(GameAction::Flap));
+        assert!(actions.pressed(GameAction::Flap));
+    }
+
+    #[test]
+    fn bound_buttons_are_read_through_the_closure() {
+        let mut actions = ActionState::default();
+        actions.update(|action| action == GameAction::Back);
+        assert!(actions.just_pressed(GameAction::Back));
+        assert!(!actions.pressed(GameAction::Flap));
+    }
+}
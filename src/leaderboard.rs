@@ -0,0 +1,835 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::{
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+};
+use bevy_flappy_macros::hex_to_color;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    AppState, GameOverLayer, NEW_BEST_COLOR, PlayerCount, Score,
+    course::{Course, CourseSelection, DifficultyPreset},
+    ghost::{LaunchedReplay, Replay, RunRecorder, save_run},
+    level::ActiveLevel,
+    ui::setup_gameover,
+};
+use crate::input::{ActionState, GameAction};
+use crate::locale::Phrase;
+use crate::profile::{ActiveProfile, Profiles};
+use crate::settings::UserSettings;
+use crate::storage;
+use crate::ui::navigation::FocusedButton;
+
+const LEADERBOARD_FILE: &str = "leaderboard.ron";
+pub const LEADERBOARD_SIZE: usize = 10;
+const MAX_NAME_LEN: usize = 10;
+const NAME_CHARS: &[u8; 36] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+// Keeps a flap held through the crash from submitting the name straight away
+const NAME_ENTRY_DELAY: f32 = 0.5;
+
+const MENU_BG_COLOR: Color = hex_to_color!("#e4ede6");
+const BUTTON_COLOR_IDLE: Color = hex_to_color!("#c3d8d2");
+const BUTTON_COLOR_HOVER: Color = hex_to_color!("#f4f5f4");
+const BUTTON_COLOR_PRESSED: Color = hex_to_color!("#c3d8d2");
+const TEXT_COLOR: Color = hex_to_color!("#2f3e46");
+const INPUT_BG_COLOR: Color = hex_to_color!("#ffffff");
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GameMode {
+    #[default]
+    Solo,
+    Multiplayer,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 2] = [Self::Solo, Self::Multiplayer];
+
+    pub fn from_players(players: usize) -> Self {
+        if players > 1 {
+            Self::Multiplayer
+        } else {
+            Self::Solo
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Solo => "Solo",
+            Self::Multiplayer => "Multiplayer",
+        }
+    }
+
+    pub fn phrase(&self) -> Phrase {
+        match self {
+            Self::Solo => Phrase::Solo,
+            Self::Multiplayer => Phrase::Multiplayer,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub score: u32,
+    // Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub seed: u32,
+    // Saved replay of the run, solo runs only
+    pub replay: Option<String>,
+    // Profile whose replays hold the run. Runs from before profiles have none and went
+    // to the first profile.
+    #[serde(default)]
+    pub profile: String,
+}
+
+impl LeaderboardEntry {
+    pub fn replay_profile<'a>(&'a self, profiles: &'a Profiles) -> &'a str {
+        match self.profile.as_str() {
+            "" => profiles.entries.first().map_or("", |first| &first.id),
+            profile => profile,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Board {
+    pub mode: GameMode,
+    pub difficulty: DifficultyPreset,
+    // Highest score first
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct Leaderboard {
+    pub boards: Vec<Board>,
+    // Offered again the next time a run qualifies
+    pub last_name: String,
+}
+
+impl Leaderboard {
+    pub fn load() -> Self {
+        storage::load(LEADERBOARD_FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Err(err) = storage::save(LEADERBOARD_FILE, self) {
+            warn!("Failed to save leaderboard: {err}");
+        }
+    }
+
+    pub fn entries(&self, mode: GameMode, difficulty: DifficultyPreset) -> &[LeaderboardEntry] {
+        self.boards
+            .iter()
+            .find(|board| board.mode == mode && board.difficulty == difficulty)
+            .map(|board| board.entries.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn qualifies(&self, mode: GameMode, difficulty: DifficultyPreset, score: u32) -> bool {
+        let entries = self.entries(mode, difficulty);
+        score > 0
+            && (entries.len() < LEADERBOARD_SIZE
+                || entries.last().is_some_and(|last| score > last.score))
+    }
+
+    // Adds the entry and returns its rank, starting at 1, with the entries it pushed off
+    // the board
+    pub fn insert(
+        &mut self,
+        mode: GameMode,
+        difficulty: DifficultyPreset,
+        entry: LeaderboardEntry,
+    ) -> (usize, Vec<LeaderboardEntry>) {
+        let index = match self
+            .boards
+            .iter()
+            .position(|board| board.mode == mode && board.difficulty == difficulty)
+        {
+            Some(index) => index,
+            None => {
+                self.boards.push(Board {
+                    mode,
+                    difficulty,
+                    entries: Vec::new(),
+                });
+                self.boards.len() - 1
+            }
+        };
+
+        let entries = &mut self.boards[index].entries;
+        // Ties go to the earlier run
+        let rank = entries.partition_point(|other| other.score >= entry.score);
+        entries.insert(rank, entry);
+        let dropped = if entries.len() > LEADERBOARD_SIZE {
+            entries.split_off(LEADERBOARD_SIZE)
+        } else {
+            Vec::new()
+        };
+        (rank + 1, dropped)
+    }
+}
+
+// Run waiting for a name before it goes on the leaderboard
+pub struct PendingEntry {
+    pub mode: GameMode,
+    pub difficulty: DifficultyPreset,
+    pub score: u32,
+    pub seed: u32,
+    pub replay: Option<Replay>,
+}
+
+#[derive(Resource)]
+pub struct NameEntry {
+    pub pending: Option<PendingEntry>,
+    pub name: String,
+    pub status: String,
+    pub delay: Timer,
+}
+
+impl Default for NameEntry {
+    fn default() -> Self {
+        Self {
+            pending: None,
+            name: String::new(),
+            status: String::new(),
+            delay: Timer::from_seconds(NAME_ENTRY_DELAY, TimerMode::Once),
+        }
+    }
+}
+
+// Board shown on the leaderboard screen
+#[derive(Resource, Default)]
+pub struct LeaderboardView {
+    pub mode: GameMode,
+    pub difficulty: DifficultyPreset,
+    pub status: String,
+}
+
+type QueryButton<'w, 's, 'a> = Query<
+    'w,
+    's,
+    (
+        &'a Interaction,
+        &'a LeaderboardOption,
+        &'a mut BackgroundColor,
+    ),
+    (Changed<Interaction>, With<Button>),
+>;
+
+#[derive(Component)]
+pub struct LeaderboardMenu;
+
+#[derive(Component)]
+pub struct LeaderboardList;
+
+#[derive(Component)]
+pub struct LeaderboardStatusText;
+
+#[derive(Component)]
+pub struct NamePrompt;
+
+#[derive(Component)]
+pub struct NameText;
+
+#[derive(Component, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum LeaderboardOption {
+    Mode,
+    Difficulty,
+    Entry(usize),
+    Back,
+}
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Leaderboard::load())
+            .init_resource::<NameEntry>()
+            .init_resource::<LeaderboardView>()
+            .add_systems(
+                OnEnter(AppState::GameOver),
+                (
+                    check_qualifies.before(setup_gameover).before(save_run),
+                    spawn_name_prompt.after(setup_gameover),
+                ),
+            )
+            .add_systems(
+                Update,
+                (handle_name_entry, update_name_prompt)
+                    .chain()
+                    .run_if(in_state(AppState::GameOver)),
+            )
+            .add_systems(OnExit(AppState::GameOver), discard_name_entry)
+            .add_systems(OnEnter(AppState::Leaderboard), setup)
+            .add_systems(
+                Update,
+                (handle_input, update_list)
+                    .chain()
+                    .run_if(in_state(AppState::Leaderboard)),
+            )
+            .add_systems(OnExit(AppState::Leaderboard), cleanup)
+            // Launched replays are raced until the player is back at the menu
+            .add_systems(OnEnter(AppState::MainMenu), clear_launched_replay)
+            .add_systems(Update, save_leaderboard);
+    }
+}
+
+fn save_leaderboard(leaderboard: Res<Leaderboard>) {
+    if leaderboard.is_changed() && !leaderboard.is_added() {
+        leaderboard.save();
+    }
+}
+
+fn clear_launched_replay(mut launched: ResMut<LaunchedReplay>) {
+    launched.0 = None;
+}
+
+#[allow(clippy::too_many_arguments)]
+fn check_qualifies(
+    score: Res<Score>,
+    course: Res<Course>,
+    active_level: Res<ActiveLevel>,
+    player_count: Res<PlayerCount>,
+    recorder: Res<RunRecorder>,
+    leaderboard: Res<Leaderboard>,
+    settings: Res<UserSettings>,
+    mut name_entry: ResMut<NameEntry>,
+    mut focused: ResMut<FocusedButton>,
+) {
+    // Only generated courses scored one point per pipe are ranked
+    if active_level.level.is_some() || settings.precision_scoring {
+        return;
+    }
+
+    let mode = GameMode::from_players(player_count.0);
+    let difficulty = course.settings.preset;
+    if !leaderboard.qualifies(mode, difficulty, score.0) {
+        return;
+    }
+
+    let replay = recorder.enabled.then(|| Replay {
+        score: score.0,
+        ..recorder.replay.clone()
+    });
+    *name_entry = NameEntry {
+        pending: Some(PendingEntry {
+            mode,
+            difficulty,
+            score: score.0,
+            seed: course.settings.seed,
+            replay,
+        }),
+        name: leaderboard.last_name.clone(),
+        ..default()
+    };
+    // Navigation keys edit the name until it is saved
+    focused.locked = true;
+}
+
+fn spawn_name_prompt(
+    mut commands: Commands,
+    name_entry: Res<NameEntry>,
+    settings: Res<UserSettings>,
+    layer_query: Single<(Entity, &Children), With<GameOverLayer>>,
+) {
+    if name_entry.pending.is_none() {
+        return;
+    }
+
+    let language = settings.language;
+    let prompt = commands
+        .spawn((
+            NamePrompt,
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                margin: UiRect::top(Val::Px(10.0)),
+                ..default()
+            },
+            children![
+                (
+                    Text(language.translate(Phrase::NewHighScore).to_string()),
+                    TextFont {
+                        font_size: 22.0,
+                        ..default()
+                    },
+                    TextColor(NEW_BEST_COLOR),
+                ),
+                (
+                    Node {
+                        min_width: Val::Px(180.0),
+                        margin: UiRect::all(Val::Px(6.0)),
+                        padding: UiRect::all(Val::Px(6.0)),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    BorderRadius::all(Val::Px(6.0)),
+                    BackgroundColor(INPUT_BG_COLOR),
+                    children![(
+                        NameText,
+                        Text::default(),
+                        TextFont {
+                            font_size: 26.0,
+                            ..default()
+                        },
+                        TextColor(TEXT_COLOR),
+                    )],
+                ),
+                (
+                    Text(language.translate(Phrase::NameEntryHelp).to_string()),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                ),
+            ],
+        ))
+        .id();
+
+    // Just above the buttons
+    let (layer, children) = layer_query.into_inner();
+    commands
+        .entity(layer)
+        .insert_children(children.len().saturating_sub(1), &[prompt]);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_name_entry(
+    time: Res<Time>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    actions: Res<ActionState>,
+    mut name_entry: ResMut<NameEntry>,
+    mut leaderboard: ResMut<Leaderboard>,
+    mut focused: ResMut<FocusedButton>,
+    active: Res<ActiveProfile>,
+    profiles: Res<Profiles>,
+    settings: Res<UserSettings>,
+) {
+    if name_entry.pending.is_none() {
+        return;
+    }
+    if !name_entry.delay.tick(time.delta()).finished() {
+        keyboard_events.clear();
+        return;
+    }
+
+    let name = &mut name_entry.name;
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Character(chars) => {
+                for c in chars.chars().filter(char::is_ascii_alphanumeric) {
+                    if name.len() < MAX_NAME_LEN {
+                        name.push(c.to_ascii_uppercase());
+                    }
+                }
+            }
+            Key::Backspace => {
+                name.pop();
+            }
+            _ => {}
+        }
+    }
+
+    // Gamepad style editing of the last letter
+    if actions.just_pressed(GameAction::NavigateRight) && name.len() < MAX_NAME_LEN {
+        name.push('A');
+    }
+    if actions.just_pressed(GameAction::NavigateLeft) {
+        name.pop();
+    }
+    let step = if actions.just_pressed(GameAction::NavigateUp) {
+        1
+    } else if actions.just_pressed(GameAction::NavigateDown) {
+        NAME_CHARS.len() - 1
+    } else {
+        0
+    };
+    if step > 0 {
+        let current = name
+            .pop()
+            .and_then(|c| NAME_CHARS.iter().position(|n| *n as char == c));
+        let next = current.map_or(0, |index| (index + step) % NAME_CHARS.len());
+        name.push(NAME_CHARS[next] as char);
+    }
+
+    if !actions.just_pressed(GameAction::Confirm) || name.is_empty() {
+        return;
+    }
+
+    let name = name.clone();
+    let Some(pending) = name_entry.pending.take() else {
+        return;
+    };
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+
+    let replay = pending.replay.and_then(|replay| {
+        let replay_name = format!("{name}_{}_{timestamp}", pending.score);
+        match replay.save(&active.0, &replay_name) {
+            Ok(()) => Some(replay_name),
+            Err(err) => {
+                warn!("Failed to save leaderboard replay: {err}");
+                None
+            }
+        }
+    });
+
+    let (rank, dropped) = leaderboard.insert(
+        pending.mode,
+        pending.difficulty,
+        LeaderboardEntry {
+            name: name.clone(),
+            score: pending.score,
+            timestamp,
+            seed: pending.seed,
+            replay,
+            profile: active.0.clone(),
+        },
+    );
+    // Runs that fell off the board take their replays with them
+    for entry in dropped {
+        if let Some(replay) = &entry.replay
+            && let Err(err) = Replay::remove(entry.replay_profile(&profiles), replay)
+        {
+            warn!("Failed to delete the replay of a dropped leaderboard run: {err}");
+        }
+    }
+    leaderboard.last_name = name;
+    name_entry.status = settings.language.fill(Phrase::SavedAtRank, &[&rank]);
+    focused.locked = false;
+}
+
+fn update_name_prompt(name_entry: Res<NameEntry>, name_query: Query<&mut Text, With<NameText>>) {
+    if !name_entry.is_changed() {
+        return;
+    }
+
+    let label = if name_entry.pending.is_some() {
+        format!("{}_", name_entry.name)
+    } else {
+        name_entry.status.clone()
+    };
+    for mut text in name_query {
+        text.0.clone_from(&label);
+    }
+}
+
+fn discard_name_entry(mut name_entry: ResMut<NameEntry>, mut focused: ResMut<FocusedButton>) {
+    if name_entry.pending.take().is_some() {
+        focused.locked = false;
+    }
+}
+
+fn setup(mut commands: Commands, mut view: ResMut<LeaderboardView>, settings: Res<UserSettings>) {
+    let language = settings.language;
+    view.status.clear();
+
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(MENU_BG_COLOR),
+        LeaderboardMenu,
+        children![
+            (
+                Text(language.translate(Phrase::Leaderboard).to_string()),
+                TextFont {
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(TEXT_COLOR),
+            ),
+            (
+                Node {
+                    width: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![
+                    create_button(LeaderboardOption::Mode, String::new(), Val::Percent(40.0)),
+                    create_button(
+                        LeaderboardOption::Difficulty,
+                        String::new(),
+                        Val::Percent(40.0),
+                    ),
+                ],
+            ),
+            (
+                LeaderboardList,
+                Node {
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+            ),
+            (
+                LeaderboardStatusText,
+                Text::default(),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(TEXT_COLOR),
+            ),
+            create_button(
+                LeaderboardOption::Back,
+                language.translate(Phrase::Back).to_string(),
+                Val::Percent(40.0),
+            ),
+        ],
+    ));
+}
+
+fn create_button(option: LeaderboardOption, label: String, width: Val) -> impl Bundle {
+    (
+        Node {
+            width,
+            height: Val::Px(30.0),
+            margin: UiRect::all(Val::Px(4.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(BUTTON_COLOR_IDLE),
+        Button,
+        option,
+        children![(
+            Text(label),
+            TextFont {
+                font_size: 16.0,
+                ..default()
+            },
+        )],
+    )
+}
+
+fn cleanup(mut commands: Commands, query: Query<Entity, With<LeaderboardMenu>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_input(
+    mut interaction_query: QueryButton,
+    actions: Res<ActionState>,
+    leaderboard: Res<Leaderboard>,
+    profiles: Res<Profiles>,
+    mut view: ResMut<LeaderboardView>,
+    mut course_selection: ResMut<CourseSelection>,
+    mut player_count: ResMut<PlayerCount>,
+    mut launched: ResMut<LaunchedReplay>,
+    settings: Res<UserSettings>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    if actions.just_pressed(GameAction::Back) {
+        app_state.set(AppState::MainMenu);
+    }
+
+    for (interaction, option, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                match option {
+                    LeaderboardOption::Mode => {
+                        view.mode = next(&GameMode::ALL, view.mode);
+                    }
+                    LeaderboardOption::Difficulty => {
+                        view.difficulty = next(&DifficultyPreset::ALL, view.difficulty);
+                    }
+                    LeaderboardOption::Entry(index) => {
+                        let entry = &leaderboard.entries(view.mode, view.difficulty)[*index];
+                        let replay = entry.replay.as_deref().and_then(|name| {
+                            let replay = Replay::load(entry.replay_profile(&profiles), name)?;
+                            Some((replay.course_settings()?, replay))
+                        });
+                        match replay {
+                            Some((course, replay)) => {
+                                launched.0 = Some(replay);
+                                *course_selection = CourseSelection::Fixed(course);
+                                player_count.0 = 1;
+                                app_state.set(AppState::InGame);
+                            }
+                            None => {
+                                view.status = settings
+                                    .language
+                                    .translate(Phrase::ReplayMissing)
+                                    .to_string();
+                            }
+                        }
+                    }
+                    LeaderboardOption::Back => {
+                        app_state.set(AppState::MainMenu);
+                    }
+                }
+                *bg_color = BackgroundColor(BUTTON_COLOR_PRESSED);
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(BUTTON_COLOR_HOVER);
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(BUTTON_COLOR_IDLE);
+            }
+        }
+    }
+}
+
+fn next<T: Copy + PartialEq>(all: &[T], current: T) -> T {
+    let index = all.iter().position(|item| *item == current).unwrap_or(0);
+    all[(index + 1) % all.len()]
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn update_list(
+    mut commands: Commands,
+    leaderboard: Res<Leaderboard>,
+    view: Res<LeaderboardView>,
+    settings: Res<UserSettings>,
+    list_query: Single<(Entity, Ref<LeaderboardList>)>,
+    button_query: Query<(&LeaderboardOption, &Children)>,
+    mut text_query: Query<&mut Text, Without<LeaderboardStatusText>>,
+    status_query: Single<&mut Text, With<LeaderboardStatusText>>,
+) {
+    let (list, marker) = list_query.into_inner();
+    if !view.is_changed() && !leaderboard.is_changed() && !marker.is_added() {
+        return;
+    }
+    let language = settings.language;
+    status_query.into_inner().0.clone_from(&view.status);
+
+    for (option, children) in &button_query {
+        let label = match option {
+            LeaderboardOption::Mode => language.translate(view.mode.phrase()).to_string(),
+            LeaderboardOption::Difficulty => {
+                language.translate(view.difficulty.phrase()).to_string()
+            }
+            LeaderboardOption::Entry(_) | LeaderboardOption::Back => continue,
+        };
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0.clone_from(&label);
+            }
+        }
+    }
+
+    // Rows are rebuilt so only runs with a replay become buttons
+    commands.entity(list).despawn_related::<Children>();
+    let entries = leaderboard.entries(view.mode, view.difficulty);
+    commands.entity(list).with_children(|parent| {
+        if entries.is_empty() {
+            parent.spawn((
+                Text(language.translate(Phrase::NoRunsYet).to_string()),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(TEXT_COLOR),
+            ));
+        }
+
+        for (index, entry) in entries.iter().enumerate() {
+            let label = format!(
+                "{}. {}  {}  {}  {} {:08X}",
+                index + 1,
+                entry.name,
+                entry.score,
+                format_date(entry.timestamp),
+                language.translate(Phrase::Seed),
+                entry.seed
+            );
+            if entry.replay.is_some() {
+                parent.spawn(create_button(
+                    LeaderboardOption::Entry(index),
+                    label,
+                    Val::Percent(90.0),
+                ));
+            } else {
+                parent.spawn((
+                    Node {
+                        height: Val::Px(30.0),
+                        margin: UiRect::all(Val::Px(4.0)),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    Text(label),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(TEXT_COLOR),
+                ));
+            }
+        }
+    });
+}
+
+// Unix timestamp as a YYYY-MM-DD date in UTC
+fn format_date(timestamp: u64) -> String {
+    // Days to civil date, from Howard Hinnant's date algorithms
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(score: u32) -> LeaderboardEntry {
+        LeaderboardEntry {
+            name: "AAA".to_string(),
+            score,
+            timestamp: 0,
+            seed: 0,
+            replay: Some(format!("AAA_{score}_0")),
+            profile: "profile-1".to_string(),
+        }
+    }
+
+    #[test]
+    fn full_board_drops_its_lowest_entry() {
+        let mut leaderboard = Leaderboard::default();
+        let (mode, difficulty) = (GameMode::Solo, DifficultyPreset::Normal);
+        for score in 1..=LEADERBOARD_SIZE as u32 {
+            let (_, dropped) = leaderboard.insert(mode, difficulty, entry(score));
+            assert!(dropped.is_empty());
+        }
+
+        let (rank, dropped) = leaderboard.insert(mode, difficulty, entry(50));
+        assert_eq!(rank, 1);
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].score, 1);
+        assert_eq!(
+            leaderboard.entries(mode, difficulty).len(),
+            LEADERBOARD_SIZE
+        );
+    }
+}
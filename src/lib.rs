@@ -1,4 +1,17 @@
+pub mod achievements;
+pub mod controls;
+pub mod editor;
+pub mod enter_code;
 pub mod game;
+pub mod history;
+pub mod input;
+pub mod leaderboard;
+pub mod locale;
 pub mod main_menu;
+pub mod profile;
+pub mod profile_picker;
 pub mod settings;
+pub mod shop;
+pub mod stats;
+pub mod storage;
 pub mod ui;
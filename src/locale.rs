@@ -0,0 +1,746 @@
+use std::fmt::{Display, Write};
+
+use serde::{Deserialize, Serialize};
+
+// The built-in font covers ASCII only, so accents are dropped and umlauts are spelled out
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    French,
+    German,
+    Spanish,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phrase {
+    // Settings
+    Settings,
+    Skin,
+    Difficulty,
+    Language,
+    Ghost,
+    PrecisionScoring,
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    StereoWidth,
+    SoundPack,
+    Mute,
+    Fullscreen,
+    Vsync,
+    FrameLimit,
+    Off,
+    Controls,
+    Back,
+    PersonalBest,
+    LastRun,
+    Easy,
+    Normal,
+    Hard,
+    // Main menu
+    BestScore,
+    Play,
+    EnterCode,
+    Editor,
+    Shop,
+    Leaderboard,
+    Stats,
+    Quit,
+    OnePlayer,
+    Players,
+    // Game
+    Paused,
+    GameOver,
+    MainMenu,
+    Retry,
+    CourseCode,
+    NewBest,
+    Best,
+    PlayerRank,
+    BronzeMedal,
+    SilverMedal,
+    GoldMedal,
+    PlatinumMedal,
+    GhostScore,
+    AheadOfGhost,
+    BehindGhost,
+    LevelWithGhost,
+    Perfect,
+    Good,
+    Shield,
+    SlowMotion,
+    Shrink,
+    Magnet,
+    Grace,
+    // Leaderboard
+    Solo,
+    Multiplayer,
+    NewHighScore,
+    NameEntryHelp,
+    SavedAtRank,
+    ReplayMissing,
+    NoRunsYet,
+    Seed,
+    // Stats and achievements
+    GamesPlayed,
+    Flaps,
+    PipesPassed,
+    TimePlayed,
+    CoinsCollected,
+    TopPipeDeaths,
+    BottomPipeDeaths,
+    GroundDeaths,
+    DailyStreak,
+    BestDailyStreak,
+    Scores,
+    Achievements,
+    AchievementCount,
+    Unlocked,
+    Locked,
+    AchievementUnlocked,
+    // Shop
+    RedBird,
+    BlueBird,
+    DaySky,
+    NightSky,
+    SparkleTrail,
+    RainbowTrail,
+    CoinsNeeded,
+    Bought,
+    TookOff,
+    ItemEquipped,
+    Equipped,
+    Owned,
+    Coins,
+    // Controls
+    Flap,
+    FlapP2,
+    FlapP3,
+    FlapP4,
+    Pause,
+    Confirm,
+    Up,
+    Down,
+    Left,
+    Right,
+    ResetToDefaults,
+    ControlsReset,
+    Cancelled,
+    ClearedBinding,
+    AlreadyBound,
+    AlreadyTriggers,
+    NowTriggers,
+    PressBinding,
+    // Profiles
+    WhosPlaying,
+    NewProfile,
+    Rename,
+    Delete,
+    ConfirmDelete,
+    NewProfileName,
+    RenameTo,
+    // Enter code
+    EnterCourseCode,
+    Start,
+    Default,
+    Gravity,
+    Jump,
+    PipeSpeed,
+    MinGap,
+    MaxGap,
+    PipeInterval,
+    InvalidCodeCharacter,
+    CodeTooShort,
+    CodeTooLong,
+    CodeTypo,
+    UnsupportedCodeVersion,
+    UnknownPreset,
+    // Editor
+    EditorHelp,
+    LevelSaved,
+    SaveFailed,
+    NoSavedLevels,
+    LevelRenamed,
+    LevelLoaded,
+    LevelUnreadable,
+    SelectedPipe,
+    SelectedNone,
+    LevelNamePrompt,
+    OpenLevelPrompt,
+    EditorSummary,
+}
+
+impl Language {
+    pub const ALL: [Language; 4] = [Self::English, Self::French, Self::German, Self::Spanish];
+
+    // Each language is listed under its own name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::French => "Francais",
+            Self::German => "Deutsch",
+            Self::Spanish => "Espanol",
+        }
+    }
+
+    pub fn translate(&self, phrase: Phrase) -> &'static str {
+        phrase.texts()[*self as usize]
+    }
+
+    // Fills the `{}` slots of a phrase with `args`, in order
+    pub fn fill(&self, phrase: Phrase, args: &[&dyn Display]) -> String {
+        let mut text = String::new();
+        let mut args = args.iter();
+        for (index, part) in self.translate(phrase).split("{}").enumerate() {
+            if index > 0
+                && let Some(arg) = args.next()
+            {
+                let _ = write!(text, "{arg}");
+            }
+            text.push_str(part);
+        }
+        text
+    }
+}
+
+impl Phrase {
+    // English, French, German and Spanish, in the order of `Language`
+    fn texts(&self) -> [&'static str; 4] {
+        match self {
+            Self::Settings => ["Settings", "Parametres", "Einstellungen", "Ajustes"],
+            Self::Skin => ["Bird skin", "Apparence", "Vogel", "Pajaro"],
+            Self::Difficulty => ["Difficulty", "Difficulte", "Schwierigkeit", "Dificultad"],
+            Self::Language => ["Language", "Langue", "Sprache", "Idioma"],
+            Self::Ghost => ["Ghost", "Fantome", "Geist", "Fantasma"],
+            Self::PrecisionScoring => [
+                "Precision scoring",
+                "Score de precision",
+                "Praezisionswertung",
+                "Puntos de precision",
+            ],
+            Self::MasterVolume => [
+                "Master volume",
+                "Volume general",
+                "Gesamtlautstaerke",
+                "Volumen general",
+            ],
+            Self::MusicVolume => ["Music", "Musique", "Musik", "Musica"],
+            Self::SfxVolume => ["Sound effects", "Effets sonores", "Effekte", "Efectos"],
+            Self::StereoWidth => [
+                "Stereo width",
+                "Largeur stereo",
+                "Stereobreite",
+                "Amplitud estereo",
+            ],
+            Self::SoundPack => [
+                "Sound pack",
+                "Pack de sons",
+                "Soundpaket",
+                "Pack de sonidos",
+            ],
+            Self::Mute => ["Mute", "Muet", "Stumm", "Silencio"],
+            Self::Fullscreen => ["Fullscreen", "Plein ecran", "Vollbild", "Pantalla completa"],
+            Self::Vsync => ["VSync", "VSync", "VSync", "VSync"],
+            Self::FrameLimit => [
+                "Frame limit",
+                "Limite d'images",
+                "Bildrate",
+                "Limite de FPS",
+            ],
+            Self::Off => ["Off", "Non", "Aus", "No"],
+            Self::Controls => ["Controls", "Commandes", "Steuerung", "Controles"],
+            Self::Back => ["Back", "Retour", "Zurueck", "Volver"],
+            Self::PersonalBest => [
+                "Personal best",
+                "Record personnel",
+                "Persoenlicher Rekord",
+                "Mejor marca",
+            ],
+            Self::LastRun => [
+                "Last run",
+                "Derniere partie",
+                "Letzter Lauf",
+                "Ultima partida",
+            ],
+            Self::Easy => ["Easy", "Facile", "Leicht", "Facil"],
+            Self::Normal => ["Normal", "Normal", "Normal", "Normal"],
+            Self::Hard => ["Hard", "Difficile", "Schwer", "Dificil"],
+            Self::BestScore => ["Best: {}", "Record : {}", "Rekord: {}", "Record: {}"],
+            Self::Play => ["Play", "Jouer", "Spielen", "Jugar"],
+            Self::EnterCode => [
+                "Enter code",
+                "Saisir un code",
+                "Code eingeben",
+                "Introducir codigo",
+            ],
+            Self::Editor => ["Editor", "Editeur", "Editor", "Editor"],
+            Self::Shop => ["Shop", "Boutique", "Laden", "Tienda"],
+            Self::Leaderboard => ["Leaderboard", "Classement", "Bestenliste", "Clasificacion"],
+            Self::Stats => ["Stats", "Statistiques", "Statistik", "Estadisticas"],
+            Self::Quit => ["Quit", "Quitter", "Beenden", "Salir"],
+            Self::OnePlayer => ["1 player", "1 joueur", "1 Spieler", "1 jugador"],
+            Self::Players => ["{} players", "{} joueurs", "{} Spieler", "{} jugadores"],
+            Self::Paused => ["Paused", "Pause", "Pause", "Pausa"],
+            Self::GameOver => [
+                "Game Over",
+                "Partie terminee",
+                "Spiel vorbei",
+                "Fin de la partida",
+            ],
+            Self::MainMenu => [
+                "Main Menu",
+                "Menu principal",
+                "Hauptmenue",
+                "Menu principal",
+            ],
+            Self::Retry => ["Retry", "Rejouer", "Nochmal", "Reintentar"],
+            Self::CourseCode => ["Course {}", "Parcours {}", "Strecke {}", "Recorrido {}"],
+            Self::NewBest => [
+                "New best! {}",
+                "Nouveau record ! {}",
+                "Neuer Rekord! {}",
+                "Nuevo record! {}",
+            ],
+            Self::Best => ["Best {}", "Record {}", "Rekord {}", "Record {}"],
+            Self::PlayerRank => [
+                "{}. Player {}",
+                "{}. Joueur {}",
+                "{}. Spieler {}",
+                "{}. Jugador {}",
+            ],
+            Self::BronzeMedal => [
+                "Bronze medal",
+                "Medaille de bronze",
+                "Bronzemedaille",
+                "Medalla de bronce",
+            ],
+            Self::SilverMedal => [
+                "Silver medal",
+                "Medaille d'argent",
+                "Silbermedaille",
+                "Medalla de plata",
+            ],
+            Self::GoldMedal => [
+                "Gold medal",
+                "Medaille d'or",
+                "Goldmedaille",
+                "Medalla de oro",
+            ],
+            Self::PlatinumMedal => [
+                "Platinum medal",
+                "Medaille de platine",
+                "Platinmedaille",
+                "Medalla de platino",
+            ],
+            Self::GhostScore => ["Ghost: {}", "Fantome : {}", "Geist: {}", "Fantasma: {}"],
+            Self::AheadOfGhost => [
+                "Ahead of ghost +{}",
+                "Devant le fantome +{}",
+                "Vor dem Geist +{}",
+                "Por delante del fantasma +{}",
+            ],
+            Self::BehindGhost => [
+                "Behind ghost -{}",
+                "Derriere le fantome -{}",
+                "Hinter dem Geist -{}",
+                "Por detras del fantasma -{}",
+            ],
+            Self::LevelWithGhost => [
+                "Level with ghost",
+                "A egalite avec le fantome",
+                "Gleichauf mit dem Geist",
+                "Empatado con el fantasma",
+            ],
+            Self::Perfect => ["Perfect!", "Parfait !", "Perfekt!", "Perfecto!"],
+            Self::Good => ["Good", "Bien", "Gut", "Bien"],
+            Self::Shield => ["Shield", "Bouclier", "Schild", "Escudo"],
+            Self::SlowMotion => ["Slow-mo", "Ralenti", "Zeitlupe", "Camara lenta"],
+            Self::Shrink => ["Shrink", "Mini", "Schrumpfen", "Encoger"],
+            Self::Magnet => ["Magnet", "Aimant", "Magnet", "Iman"],
+            Self::Grace => ["Grace", "Sursis", "Schonfrist", "Gracia"],
+            Self::Solo => ["Solo", "Solo", "Solo", "Solo"],
+            Self::Multiplayer => ["Multiplayer", "Multijoueur", "Mehrspieler", "Multijugador"],
+            Self::NewHighScore => [
+                "New high score! Enter your name",
+                "Nouveau record ! Entrez votre nom",
+                "Neuer Highscore! Gib deinen Namen ein",
+                "Nuevo record! Escribe tu nombre",
+            ],
+            Self::NameEntryHelp => [
+                "Type or use Up/Down and Left/Right, then Confirm",
+                "Tapez ou utilisez Haut/Bas et Gauche/Droite, puis Valider",
+                "Tippen oder Hoch/Runter und Links/Rechts, dann Bestaetigen",
+                "Escribe o usa Arriba/Abajo e Izquierda/Derecha, luego Confirmar",
+            ],
+            Self::SavedAtRank => [
+                "Saved at #{} on the leaderboard",
+                "Enregistre a la place {} du classement",
+                "Auf Platz {} der Bestenliste gespeichert",
+                "Guardado en el puesto {} de la clasificacion",
+            ],
+            Self::ReplayMissing => [
+                "The replay of this run is missing",
+                "Le replay de cette partie est introuvable",
+                "Die Aufzeichnung dieses Laufs fehlt",
+                "Falta la repeticion de esta partida",
+            ],
+            Self::NoRunsYet => [
+                "No runs yet",
+                "Aucune partie pour l'instant",
+                "Noch keine Laeufe",
+                "Aun no hay partidas",
+            ],
+            Self::Seed => ["seed", "graine", "Seed", "semilla"],
+            Self::GamesPlayed => [
+                "Games played: {}",
+                "Parties jouees : {}",
+                "Gespielte Spiele: {}",
+                "Partidas jugadas: {}",
+            ],
+            Self::Flaps => [
+                "Flaps: {}",
+                "Battements : {}",
+                "Fluegelschlaege: {}",
+                "Aleteos: {}",
+            ],
+            Self::PipesPassed => [
+                "Pipes passed: {}",
+                "Tuyaux passes : {}",
+                "Passierte Rohre: {}",
+                "Tubos superados: {}",
+            ],
+            Self::TimePlayed => [
+                "Time played: {}",
+                "Temps de jeu : {}",
+                "Spielzeit: {}",
+                "Tiempo jugado: {}",
+            ],
+            Self::CoinsCollected => [
+                "Coins collected: {}",
+                "Pieces ramassees : {}",
+                "Gesammelte Muenzen: {}",
+                "Monedas recogidas: {}",
+            ],
+            Self::TopPipeDeaths => [
+                "Top pipe deaths: {}",
+                "Chutes sur tuyau du haut : {}",
+                "Tode am oberen Rohr: {}",
+                "Choques con tubo superior: {}",
+            ],
+            Self::BottomPipeDeaths => [
+                "Bottom pipe deaths: {}",
+                "Chutes sur tuyau du bas : {}",
+                "Tode am unteren Rohr: {}",
+                "Choques con tubo inferior: {}",
+            ],
+            Self::GroundDeaths => [
+                "Ground deaths: {}",
+                "Chutes au sol : {}",
+                "Tode am Boden: {}",
+                "Choques con el suelo: {}",
+            ],
+            Self::DailyStreak => [
+                "Daily streak: {}",
+                "Serie quotidienne : {}",
+                "Tagesserie: {}",
+                "Racha diaria: {}",
+            ],
+            Self::BestDailyStreak => [
+                "Best daily streak: {}",
+                "Meilleure serie : {}",
+                "Beste Tagesserie: {}",
+                "Mejor racha diaria: {}",
+            ],
+            Self::Scores => [
+                "Scores: {}",
+                "Scores : {}",
+                "Punkte: {}",
+                "Puntuaciones: {}",
+            ],
+            Self::Achievements => ["Achievements", "Succes", "Erfolge", "Logros"],
+            Self::AchievementCount => [
+                "Achievements {}/{}",
+                "Succes {}/{}",
+                "Erfolge {}/{}",
+                "Logros {}/{}",
+            ],
+            Self::Unlocked => ["Unlocked", "Debloque", "Freigeschaltet", "Desbloqueado"],
+            Self::Locked => ["Locked", "Verrouille", "Gesperrt", "Bloqueado"],
+            Self::AchievementUnlocked => [
+                "Achievement unlocked",
+                "Succes debloque",
+                "Erfolg freigeschaltet",
+                "Logro desbloqueado",
+            ],
+            Self::RedBird => ["Red bird", "Oiseau rouge", "Roter Vogel", "Pajaro rojo"],
+            Self::BlueBird => ["Blue bird", "Oiseau bleu", "Blauer Vogel", "Pajaro azul"],
+            Self::DaySky => ["Day sky", "Ciel de jour", "Tageshimmel", "Cielo de dia"],
+            Self::NightSky => ["Night sky", "Ciel de nuit", "Nachthimmel", "Cielo nocturno"],
+            Self::SparkleTrail => [
+                "Sparkle trail",
+                "Trainee scintillante",
+                "Funkelspur",
+                "Estela brillante",
+            ],
+            Self::RainbowTrail => [
+                "Rainbow trail",
+                "Trainee arc-en-ciel",
+                "Regenbogenspur",
+                "Estela arcoiris",
+            ],
+            Self::CoinsNeeded => [
+                "{} more coins needed",
+                "Il manque {} pieces",
+                "Noch {} Muenzen noetig",
+                "Faltan {} monedas",
+            ],
+            Self::Bought => [
+                "Bought {}!",
+                "{} achete !",
+                "{} gekauft!",
+                "Has comprado {}!",
+            ],
+            Self::TookOff => ["Took off {}", "{} retire", "{} abgelegt", "{} quitado"],
+            Self::ItemEquipped => ["{} equipped", "{} equipe", "{} ausgeruestet", "{} equipado"],
+            Self::Equipped => ["Equipped", "Equipe", "Ausgeruestet", "Equipado"],
+            Self::Owned => ["Owned", "Possede", "Im Besitz", "En propiedad"],
+            Self::Coins => ["{} coins", "{} pieces", "{} Muenzen", "{} monedas"],
+            Self::Flap => ["Flap", "Battre", "Flattern", "Aletear"],
+            Self::FlapP2 => ["Flap P2", "Battre J2", "Flattern S2", "Aletear J2"],
+            Self::FlapP3 => ["Flap P3", "Battre J3", "Flattern S3", "Aletear J3"],
+            Self::FlapP4 => ["Flap P4", "Battre J4", "Flattern S4", "Aletear J4"],
+            Self::Pause => ["Pause", "Pause", "Pause", "Pausa"],
+            Self::Confirm => ["Confirm", "Valider", "Bestaetigen", "Confirmar"],
+            Self::Up => ["Up", "Haut", "Hoch", "Arriba"],
+            Self::Down => ["Down", "Bas", "Runter", "Abajo"],
+            Self::Left => ["Left", "Gauche", "Links", "Izquierda"],
+            Self::Right => ["Right", "Droite", "Rechts", "Derecha"],
+            Self::ResetToDefaults => [
+                "Reset to defaults",
+                "Reinitialiser",
+                "Zuruecksetzen",
+                "Restablecer",
+            ],
+            Self::ControlsReset => [
+                "Controls reset to defaults",
+                "Commandes reinitialisees",
+                "Steuerung zurueckgesetzt",
+                "Controles restablecidos",
+            ],
+            Self::Cancelled => ["Cancelled", "Annule", "Abgebrochen", "Cancelado"],
+            Self::ClearedBinding => [
+                "Cleared a binding of {}",
+                "Touche de {} retiree",
+                "Belegung von {} entfernt",
+                "Asignacion de {} borrada",
+            ],
+            Self::AlreadyBound => [
+                "{} is already bound to {}",
+                "{} est deja assigne a {}",
+                "{} ist schon mit {} belegt",
+                "{} ya esta asignado a {}",
+            ],
+            Self::AlreadyTriggers => [
+                "{} already triggers {}",
+                "{} declenche deja {}",
+                "{} loest schon {} aus",
+                "{} ya activa {}",
+            ],
+            Self::NowTriggers => [
+                "{} now triggers {}",
+                "{} declenche maintenant {}",
+                "{} loest jetzt {} aus",
+                "{} ahora activa {}",
+            ],
+            Self::PressBinding => [
+                "Press a key or button for {} (Esc cancels, Backspace clears)",
+                "Appuyez sur une touche ou un bouton pour {} (Echap annule, Retour efface)",
+                "Taste oder Knopf fuer {} druecken (Esc bricht ab, Ruecktaste loescht)",
+                "Pulsa una tecla o boton para {} (Esc cancela, Retroceso borra)",
+            ],
+            Self::WhosPlaying => [
+                "Who's playing?",
+                "Qui joue ?",
+                "Wer spielt?",
+                "Quien juega?",
+            ],
+            Self::NewProfile => [
+                "New profile",
+                "Nouveau profil",
+                "Neues Profil",
+                "Nuevo perfil",
+            ],
+            Self::Rename => ["Rename", "Renommer", "Umbenennen", "Renombrar"],
+            Self::Delete => ["Delete", "Supprimer", "Loeschen", "Borrar"],
+            Self::ConfirmDelete => ["Sure?", "Sur ?", "Sicher?", "Seguro?"],
+            Self::NewProfileName => [
+                "New profile: {}_",
+                "Nouveau profil : {}_",
+                "Neues Profil: {}_",
+                "Nuevo perfil: {}_",
+            ],
+            Self::RenameTo => [
+                "Rename to: {}_",
+                "Renommer en : {}_",
+                "Umbenennen in: {}_",
+                "Renombrar a: {}_",
+            ],
+            Self::EnterCourseCode => [
+                "Enter course code",
+                "Saisir un code de parcours",
+                "Streckencode eingeben",
+                "Introduce un codigo de recorrido",
+            ],
+            Self::Start => ["Start", "Commencer", "Starten", "Empezar"],
+            Self::Default => ["Default", "Par defaut", "Standard", "Por defecto"],
+            Self::Gravity => ["Gravity", "Gravite", "Schwerkraft", "Gravedad"],
+            Self::Jump => ["Jump", "Saut", "Sprung", "Salto"],
+            Self::PipeSpeed => [
+                "Pipe speed",
+                "Vitesse des tuyaux",
+                "Rohrtempo",
+                "Velocidad de tubos",
+            ],
+            Self::MinGap => ["Min gap", "Ecart min", "Min. Luecke", "Hueco min"],
+            Self::MaxGap => ["Max gap", "Ecart max", "Max. Luecke", "Hueco max"],
+            Self::PipeInterval => [
+                "Pipe interval",
+                "Intervalle des tuyaux",
+                "Rohrabstand",
+                "Intervalo de tubos",
+            ],
+            Self::InvalidCodeCharacter => [
+                "'{}' is not a valid code character",
+                "'{}' n'est pas un caractere de code valide",
+                "'{}' ist kein gueltiges Codezeichen",
+                "'{}' no es un caracter de codigo valido",
+            ],
+            Self::CodeTooShort => [
+                "Code is too short",
+                "Le code est trop court",
+                "Der Code ist zu kurz",
+                "El codigo es demasiado corto",
+            ],
+            Self::CodeTooLong => [
+                "Code is too long",
+                "Le code est trop long",
+                "Der Code ist zu lang",
+                "El codigo es demasiado largo",
+            ],
+            Self::CodeTypo => [
+                "Code has a typo",
+                "Le code contient une faute de frappe",
+                "Der Code hat einen Tippfehler",
+                "El codigo tiene una errata",
+            ],
+            Self::UnsupportedCodeVersion => [
+                "Unsupported code version {}",
+                "Version de code {} non prise en charge",
+                "Codeversion {} wird nicht unterstuetzt",
+                "Version de codigo {} no compatible",
+            ],
+            Self::UnknownPreset => [
+                "Unknown difficulty preset {}",
+                "Niveau de difficulte {} inconnu",
+                "Unbekannte Schwierigkeitsstufe {}",
+                "Nivel de dificultad {} desconocido",
+            ],
+            Self::EditorHelp => [
+                "Click: place/select  Drag: move gap  Right click: delete\n\
+                 A/D, wheel: scroll  [ ]: gap size  V: variant  M: movement\n\
+                 P: test-play  F2: name  Ctrl+S/O: save/open  Esc: menu",
+                "Clic : placer/choisir  Glisser : deplacer l'ecart  Clic droit : supprimer\n\
+                 A/D, molette : defiler  [ ] : taille de l'ecart  V : variante  M : mouvement\n\
+                 P : tester  F2 : nom  Ctrl+S/O : enregistrer/ouvrir  Echap : menu",
+                "Klick: setzen/waehlen  Ziehen: Luecke verschieben  Rechtsklick: loeschen\n\
+                 A/D, Mausrad: scrollen  [ ]: Lueckengroesse  V: Variante  M: Bewegung\n\
+                 P: testen  F2: Name  Strg+S/O: speichern/oeffnen  Esc: Menue",
+                "Clic: colocar/elegir  Arrastrar: mover hueco  Clic derecho: borrar\n\
+                 A/D, rueda: desplazar  [ ]: tamano del hueco  V: variante  M: movimiento\n\
+                 P: probar  F2: nombre  Ctrl+S/O: guardar/abrir  Esc: menu",
+            ],
+            Self::LevelSaved => [
+                "Saved '{}'",
+                "'{}' enregistre",
+                "'{}' gespeichert",
+                "'{}' guardado",
+            ],
+            Self::SaveFailed => [
+                "Save failed: {}",
+                "Echec de l'enregistrement : {}",
+                "Speichern fehlgeschlagen: {}",
+                "Error al guardar: {}",
+            ],
+            Self::NoSavedLevels => [
+                "No saved levels yet",
+                "Aucun niveau enregistre",
+                "Noch keine gespeicherten Level",
+                "Aun no hay niveles guardados",
+            ],
+            Self::LevelRenamed => [
+                "Renamed to '{}', Ctrl+S to save",
+                "Renomme en '{}', Ctrl+S pour enregistrer",
+                "In '{}' umbenannt, Strg+S zum Speichern",
+                "Renombrado a '{}', Ctrl+S para guardar",
+            ],
+            Self::LevelLoaded => ["Loaded '{}'", "'{}' charge", "'{}' geladen", "'{}' cargado"],
+            Self::LevelUnreadable => [
+                "Couldn't read level '{}'",
+                "Impossible de lire le niveau '{}'",
+                "Level '{}' konnte nicht gelesen werden",
+                "No se pudo leer el nivel '{}'",
+            ],
+            Self::SelectedPipe => [
+                "Selected: x {}  gap {} @ {}  {}  {}",
+                "Selection : x {}  ecart {} @ {}  {}  {}",
+                "Auswahl: x {}  Luecke {} @ {}  {}  {}",
+                "Seleccion: x {}  hueco {} @ {}  {}  {}",
+            ],
+            Self::SelectedNone => [
+                "Selected: none",
+                "Selection : aucune",
+                "Auswahl: keine",
+                "Seleccion: ninguna",
+            ],
+            Self::LevelNamePrompt => [
+                "Level name: {}_  (Enter: OK  Esc: cancel)",
+                "Nom du niveau : {}_  (Entree : OK  Echap : annuler)",
+                "Levelname: {}_  (Enter: OK  Esc: abbrechen)",
+                "Nombre del nivel: {}_  (Intro: OK  Esc: cancelar)",
+            ],
+            Self::OpenLevelPrompt => [
+                "Open level (Up/Down, Enter: open  Esc: cancel)",
+                "Ouvrir un niveau (Haut/Bas, Entree : ouvrir  Echap : annuler)",
+                "Level oeffnen (Hoch/Runter, Enter: oeffnen  Esc: abbrechen)",
+                "Abrir nivel (Arriba/Abajo, Intro: abrir  Esc: cancelar)",
+            ],
+            Self::EditorSummary => [
+                "{}  |  {} pipes  |  scroll {}",
+                "{}  |  {} tuyaux  |  defilement {}",
+                "{}  |  {} Rohre  |  Scroll {}",
+                "{}  |  {} tubos  |  desplazamiento {}",
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slots_are_filled_in_order() {
+        let args: [&dyn Display; 2] = [&"Space", &"Flap"];
+        assert_eq!(
+            Language::English.fill(Phrase::AlreadyBound, &args),
+            "Space is already bound to Flap"
+        );
+        assert_eq!(
+            Language::German.fill(Phrase::AlreadyBound, &args),
+            "Space ist schon mit Flap belegt"
+        );
+        assert_eq!(Language::French.fill(Phrase::Coins, &[&12]), "12 pieces");
+        assert_eq!(Language::Spanish.translate(Phrase::Back), "Volver");
+    }
+}
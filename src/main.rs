@@ -1,11 +1,31 @@
 use bevy::{prelude::*, window::EnabledButtons};
 use bevy_flappy::{
-    game::{GAME_DIMENSIONS, GamePlugin},
+    achievements::AchievementsPlugin,
+    controls::ControlsPlugin,
+    editor::EditorPlugin,
+    enter_code::EnterCodePlugin,
+    game::{GAME_DIMENSIONS, GamePlugin, audio::EAR_GAP},
+    history::{self, HistoryPlugin},
+    input::ActionsPlugin,
+    leaderboard::LeaderboardPlugin,
     main_menu::MainMenuPlugin,
+    profile::ProfilePlugin,
+    profile_picker::ProfilePickerPlugin,
     settings::SettingsPlugin,
+    shop::ShopPlugin,
+    stats::StatsPlugin,
+    ui::{
+        digits::DigitsPlugin, navigation::NavigationPlugin, toast::ToastPlugin,
+        widgets::WidgetsPlugin,
+    },
 };
 
-fn main() {
+fn main() -> AppExit {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(exit) = history::run_export(&args) {
+        return exit;
+    }
+
     App::new()
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
@@ -20,13 +40,29 @@ fn main() {
             }),
             ..default()
         }))
+        .add_plugins(ActionsPlugin)
+        .add_plugins(NavigationPlugin)
+        .add_plugins(DigitsPlugin)
+        .add_plugins(ToastPlugin)
+        .add_plugins(WidgetsPlugin)
         .add_plugins(GamePlugin)
         .add_plugins(MainMenuPlugin)
         .add_plugins(SettingsPlugin)
+        .add_plugins(EditorPlugin)
+        .add_plugins(EnterCodePlugin)
+        .add_plugins(ControlsPlugin)
+        .add_plugins(ProfilePlugin)
+        .add_plugins(ProfilePickerPlugin)
+        .add_plugins(ShopPlugin)
+        .add_plugins(LeaderboardPlugin)
+        .add_plugins(StatsPlugin)
+        .add_plugins(HistoryPlugin)
+        .add_plugins(AchievementsPlugin)
         .add_systems(Startup, setup)
-        .run();
+        .run()
 }
 
 fn setup(mut commands: Commands) {
-    commands.spawn(Camera2d);
+    // Sound effects are panned around the camera
+    commands.spawn((Camera2d, SpatialListener::new(EAR_GAP)));
 }
@@ -1,12 +1,18 @@
 use bevy::prelude::*;
 use bevy_flappy_macros::hex_to_color;
 
-use crate::game::AppState;
+use crate::game::{
+    AppState, MAX_PLAYERS, PlayerCount, course::CourseSelection, highscore::HighScore,
+};
+use crate::locale::Phrase;
+use crate::profile::Profiles;
+use crate::settings::UserSettings;
 
 const MENU_BG_COLOR: Color = hex_to_color!("#e4ede6");
 const BUTTON_COLOR_IDLE: Color = hex_to_color!("#c3d8d2");
 const BUTTON_COLOR_HOVER: Color = hex_to_color!("#f4f5f4");
 const BUTTON_COLOR_PRESSED: Color = hex_to_color!("#c3d8d2");
+const TEXT_COLOR: Color = hex_to_color!("#2f3e46");
 
 type QueryButton<'w, 's, 'a> = Query<
     'w,
@@ -20,7 +26,14 @@ pub struct MainMenu;
 
 #[derive(Component, Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum MenuButton {
+    Profile,
     Play,
+    Players,
+    EnterCode,
+    Editor,
+    Shop,
+    Leaderboard,
+    Stats,
     Settings,
     Quit,
 }
@@ -30,21 +43,26 @@ pub struct MainMenuPlugin;
 impl Plugin for MainMenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(AppState::MainMenu), setup)
-            .add_systems(Update, handle_input)
+            .add_systems(Update, (handle_input, update_players_label).chain())
             .add_systems(OnExit(AppState::MainMenu), cleanup)
             .init_state::<AppState>();
     }
 }
 
-fn setup(mut commands: Commands) {
-    fn create_button(text: String, button_type: MenuButton) -> impl Bundle {
+fn setup(
+    mut commands: Commands,
+    high_score: Res<HighScore>,
+    profiles: Res<Profiles>,
+    settings: Res<UserSettings>,
+) {
+    fn create_button(text: &str, button_type: MenuButton) -> impl Bundle {
         (
             Button,
             Node {
                 width: Val::Percent(40.0),
-                height: Val::Percent(15.0),
-                margin: UiRect::all(Val::Px(10.0)),
-                padding: UiRect::all(Val::Px(10.0)),
+                height: Val::Percent(6.5),
+                margin: UiRect::all(Val::Px(4.0)),
+                padding: UiRect::all(Val::Px(4.0)),
                 justify_content: JustifyContent::Center,
                 align_content: AlignContent::Center,
                 justify_items: JustifyItems::Center,
@@ -60,9 +78,9 @@ fn setup(mut commands: Commands) {
                 ..default()
             }]),
             children![(
-                Text(text),
+                Text(text.to_string()),
                 TextFont {
-                    font_size: 30.0,
+                    font_size: 24.0,
                     ..default()
                 },
             )],
@@ -70,6 +88,7 @@ fn setup(mut commands: Commands) {
             button_type,
         )
     }
+    let language = settings.language;
     commands.spawn((
         Node {
             width: Val::Percent(100.0),
@@ -80,22 +99,62 @@ fn setup(mut commands: Commands) {
         },
         MainMenu,
         BackgroundColor(MENU_BG_COLOR),
-        children![(
-            Node {
-                width: Val::Percent(100.0),
-                height: Val::Percent(100.0),
-                flex_direction: FlexDirection::Column,
-                justify_content: JustifyContent::End,
-                margin: UiRect::bottom(Val::Px(50.0)),
-                align_items: AlignItems::Center,
-                ..default()
-            },
-            children![
-                create_button("Play".to_string(), MenuButton::Play),
-                create_button("Settings".to_string(), MenuButton::Settings),
-                create_button("Quit".to_string(), MenuButton::Quit),
-            ]
-        )],
+        children![
+            (
+                // Profile switcher in the corner, out of the way of the main buttons
+                Button,
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    left: Val::Px(10.0),
+                    padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                    ..default()
+                },
+                BorderRadius::all(Val::Px(10.0)),
+                BackgroundColor(BUTTON_COLOR_IDLE),
+                MenuButton::Profile,
+                children![(
+                    Text(profiles.active_name().to_string()),
+                    TextFont {
+                        font_size: 18.0,
+                        ..default()
+                    },
+                )],
+            ),
+            (
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::End,
+                    margin: UiRect::bottom(Val::Px(50.0)),
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                children![
+                    (
+                        Text(language.fill(Phrase::BestScore, &[&high_score.best])),
+                        TextFont {
+                            font_size: 28.0,
+                            ..default()
+                        },
+                        TextColor(TEXT_COLOR),
+                    ),
+                    create_button(language.translate(Phrase::Play), MenuButton::Play),
+                    create_button("", MenuButton::Players),
+                    create_button(language.translate(Phrase::EnterCode), MenuButton::EnterCode),
+                    create_button(language.translate(Phrase::Editor), MenuButton::Editor),
+                    create_button(language.translate(Phrase::Shop), MenuButton::Shop),
+                    create_button(
+                        language.translate(Phrase::Leaderboard),
+                        MenuButton::Leaderboard
+                    ),
+                    create_button(language.translate(Phrase::Stats), MenuButton::Stats),
+                    create_button(language.translate(Phrase::Settings), MenuButton::Settings),
+                    create_button(language.translate(Phrase::Quit), MenuButton::Quit),
+                ]
+            )
+        ],
     ));
 }
 
@@ -103,6 +162,8 @@ fn handle_input(
     mut interaction_query: QueryButton,
     mut exit: EventWriter<AppExit>,
     mut app_state: ResMut<NextState<AppState>>,
+    mut course_selection: ResMut<CourseSelection>,
+    mut player_count: ResMut<PlayerCount>,
 ) {
     for (interaction, mut bg_color, button_type) in &mut interaction_query {
         match *interaction {
@@ -110,9 +171,31 @@ fn handle_input(
                 *bg_color = BackgroundColor(BUTTON_COLOR_PRESSED);
 
                 match button_type {
+                    MenuButton::Profile => {
+                        app_state.set(AppState::Profiles);
+                    }
                     MenuButton::Play => {
+                        *course_selection = CourseSelection::Random;
                         app_state.set(AppState::InGame);
                     }
+                    MenuButton::Players => {
+                        player_count.0 = player_count.0 % MAX_PLAYERS + 1;
+                    }
+                    MenuButton::EnterCode => {
+                        app_state.set(AppState::EnterCode);
+                    }
+                    MenuButton::Editor => {
+                        app_state.set(AppState::Editor);
+                    }
+                    MenuButton::Shop => {
+                        app_state.set(AppState::Shop);
+                    }
+                    MenuButton::Leaderboard => {
+                        app_state.set(AppState::Leaderboard);
+                    }
+                    MenuButton::Stats => {
+                        app_state.set(AppState::Stats);
+                    }
                     MenuButton::Settings => {
                         app_state.set(AppState::Settings);
                     }
@@ -131,6 +214,28 @@ fn handle_input(
     }
 }
 
+fn update_players_label(
+    player_count: Res<PlayerCount>,
+    settings: Res<UserSettings>,
+    button_query: Query<(Ref<MenuButton>, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (button, children) in &button_query {
+        if *button != MenuButton::Players || !(player_count.is_changed() || button.is_added()) {
+            continue;
+        }
+
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = match player_count.0 {
+                    1 => settings.language.translate(Phrase::OnePlayer).to_string(),
+                    players => settings.language.fill(Phrase::Players, &[&players]),
+                };
+            }
+        }
+    }
+}
+
 fn cleanup(mut commands: Commands, query: Query<Entity, With<MainMenu>>) {
     for entity in &query {
         commands.entity(entity).despawn();
@@ -0,0 +1,269 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::achievements::{ACHIEVEMENTS_FILE, Achievements};
+use crate::game::{
+    BG_SPRITE_PATH,
+    ghost::REPLAYS_DIR,
+    highscore::{HIGH_SCORE_FILE, HighScore},
+    medals::Medal,
+    skins::DEFAULT_SKIN,
+};
+use crate::input::InputBindings;
+use crate::settings::{SETTINGS_FILE, UserSettings};
+use crate::shop::{ItemKind, SHOP_ITEMS, ShopItem};
+use crate::stats::{STATS_FILE, Stats};
+use crate::storage;
+
+pub const PROFILE_FILE: &str = "profile.ron";
+const PROFILES_FILE: &str = "profiles.ron";
+const DEFAULT_PROFILE_NAME: &str = "Player1";
+// Files kept for each profile, which used to sit at the top of the data directory
+const PROFILE_FILES: [&str; 5] = [
+    PROFILE_FILE,
+    SETTINGS_FILE,
+    HIGH_SCORE_FILE,
+    STATS_FILE,
+    ACHIEVEMENTS_FILE,
+];
+const NIGHT_BG_SPRITE_PATH: &str = "sprites/background-night.png";
+pub const DEFAULT_BACKGROUND: &str = "day";
+
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Profile {
+    pub coins: u32,
+    // Ids of the shop items bought so far
+    pub unlocked: Vec<String>,
+    pub background: String,
+    pub trail: Option<String>,
+    // Medals earned over every run
+    pub medals: BTreeMap<Medal, u32>,
+    // Whether the skin picked before the shop sold skins has been handed over
+    pub picked_skin_granted: bool,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            coins: 0,
+            unlocked: Vec::new(),
+            background: DEFAULT_BACKGROUND.to_string(),
+            trail: None,
+            medals: BTreeMap::new(),
+            picked_skin_granted: false,
+        }
+    }
+}
+
+impl Profile {
+    pub fn load(profile: &str) -> Self {
+        storage::load(storage::profile_path(profile, PROFILE_FILE)).unwrap_or_default()
+    }
+
+    pub fn save(&self, profile: &str) {
+        if let Err(err) = storage::save(storage::profile_path(profile, PROFILE_FILE), self) {
+            warn!("Failed to save profile: {err}");
+        }
+    }
+
+    pub fn owns(&self, item: &ShopItem) -> bool {
+        item.price == 0 || self.unlocked.iter().any(|id| id == item.id)
+    }
+
+    // Skins that aren't sold in the shop are always available
+    pub fn skin_unlocked(&self, skin: &str) -> bool {
+        SHOP_ITEMS
+            .iter()
+            .filter(|item| item.kind == ItemKind::Skin && item.id == skin)
+            .all(|item| self.owns(item))
+    }
+
+    // Skins used to be free to pick, so one chosen back then stays owned now it's sold
+    pub fn grant_picked_skin(&mut self, settings: &UserSettings) {
+        if self.picked_skin_granted {
+            return;
+        }
+        self.picked_skin_granted = true;
+
+        let sold = SHOP_ITEMS
+            .iter()
+            .any(|item| item.kind == ItemKind::Skin && item.id == settings.skin);
+        if sold && !self.unlocked.contains(&settings.skin) {
+            self.unlocked.push(settings.skin.clone());
+        }
+    }
+
+    // The chosen skin, or the default one if it hasn't been bought
+    pub fn skin<'a>(&self, settings: &'a UserSettings) -> &'a str {
+        if self.skin_unlocked(&settings.skin) {
+            &settings.skin
+        } else {
+            DEFAULT_SKIN
+        }
+    }
+
+    pub fn background_path(&self) -> &'static str {
+        match self.background.as_str() {
+            "night" => NIGHT_BG_SPRITE_PATH,
+            _ => BG_SPRITE_PATH,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProfileEntry {
+    // Name of the profile's directory, which stays the same through renames
+    pub id: String,
+    pub name: String,
+}
+
+// Everyone who plays on this machine
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct Profiles {
+    pub entries: Vec<ProfileEntry>,
+    pub active: String,
+    next_id: u32,
+}
+
+impl Profiles {
+    pub fn load() -> Self {
+        let mut profiles: Self = storage::load(PROFILES_FILE).unwrap_or_default();
+        if profiles.entries.is_empty() {
+            profiles.active = profiles.create(DEFAULT_PROFILE_NAME);
+            // Progress from before profiles existed goes to the first one
+            for file in PROFILE_FILES {
+                if let Err(err) =
+                    storage::relocate(file, storage::profile_path(&profiles.active, file))
+                {
+                    warn!("Failed to move {file} into the first profile: {err}");
+                }
+            }
+            if let Err(err) = storage::relocate(
+                REPLAYS_DIR,
+                storage::profile_path(&profiles.active, REPLAYS_DIR),
+            ) {
+                warn!("Failed to move the replays into the first profile: {err}");
+            }
+            profiles.save();
+        }
+
+        if profiles.get(&profiles.active).is_none() {
+            profiles.active = profiles.entries[0].id.clone();
+        }
+        profiles
+    }
+
+    // The saved profiles as they are, without creating or moving anything
+    pub fn read() -> Option<Self> {
+        storage::load(PROFILES_FILE)
+    }
+
+    pub fn save(&self) {
+        if let Err(err) = storage::save(PROFILES_FILE, self) {
+            warn!("Failed to save profiles: {err}");
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ProfileEntry> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    pub fn active_name(&self) -> &str {
+        self.get(&self.active).map_or("", |entry| &entry.name)
+    }
+
+    pub fn create(&mut self, name: &str) -> String {
+        self.next_id += 1;
+        let id = format!("profile-{}", self.next_id);
+        self.entries.push(ProfileEntry {
+            id: id.clone(),
+            name: name.to_string(),
+        });
+        id
+    }
+
+    pub fn rename(&mut self, id: &str, name: &str) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.id == id) {
+            entry.name = name.to_string();
+        }
+    }
+
+    // Deletes the profile and its files; the last profile can't be removed
+    pub fn remove(&mut self, id: &str) -> bool {
+        if self.entries.len() <= 1 || self.get(id).is_none() {
+            return false;
+        }
+
+        self.entries.retain(|entry| entry.id != id);
+        if self.active == id {
+            self.active = self.entries[0].id.clone();
+        }
+        if let Err(err) = storage::remove_profile(id) {
+            warn!("Failed to delete the files of profile {id}: {err}");
+        }
+        true
+    }
+}
+
+// Profile whose progress is currently loaded
+#[derive(Resource, Default)]
+pub struct ActiveProfile(pub String);
+
+pub struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Profiles::load())
+            .init_resource::<ActiveProfile>()
+            .init_resource::<Profile>()
+            // Before startup so every screen sees the progress of the chosen profile
+            .add_systems(PreStartup, load_active_profile)
+            .add_systems(
+                PreUpdate,
+                load_active_profile.run_if(resource_changed::<Profiles>),
+            )
+            .add_systems(Update, (save_profiles, save_profile));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn load_active_profile(
+    profiles: Res<Profiles>,
+    mut active: ResMut<ActiveProfile>,
+    mut profile: ResMut<Profile>,
+    mut settings: ResMut<UserSettings>,
+    mut bindings: ResMut<InputBindings>,
+    mut high_score: ResMut<HighScore>,
+    mut stats: ResMut<Stats>,
+    mut achievements: ResMut<Achievements>,
+) {
+    if active.0 == profiles.active {
+        return;
+    }
+
+    let id = &profiles.active;
+    *profile = Profile::load(id);
+    *settings = UserSettings::load(id);
+    profile.grant_picked_skin(&settings);
+    *bindings = settings.bindings.clone().with_defaults();
+    *high_score = HighScore::load(id);
+    *stats = Stats::load(id);
+    *achievements = Achievements::load(id);
+    active.0.clone_from(id);
+}
+
+fn save_profiles(profiles: Res<Profiles>) {
+    if profiles.is_changed() && !profiles.is_added() {
+        profiles.save();
+    }
+}
+
+fn save_profile(profile: Res<Profile>, active: Res<ActiveProfile>) {
+    if profile.is_changed() && !profile.is_added() {
+        profile.save(&active.0);
+    }
+}
@@ -0,0 +1,424 @@
+use bevy::{
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+};
+use bevy_flappy_macros::hex_to_color;
+
+use crate::game::AppState;
+use crate::input::{ActionState, GameAction};
+use crate::locale::Phrase;
+use crate::profile::Profiles;
+use crate::settings::UserSettings;
+use crate::ui::navigation::FocusedButton;
+
+const MENU_BG_COLOR: Color = hex_to_color!("#e4ede6");
+const BUTTON_COLOR_IDLE: Color = hex_to_color!("#c3d8d2");
+const BUTTON_COLOR_HOVER: Color = hex_to_color!("#f4f5f4");
+const BUTTON_COLOR_PRESSED: Color = hex_to_color!("#c3d8d2");
+const INPUT_BG_COLOR: Color = hex_to_color!("#ffffff");
+const TEXT_COLOR: Color = hex_to_color!("#2f3e46");
+
+const MAX_NAME_LEN: usize = 16;
+
+type QueryButton<'w, 's, 'a> = Query<
+    'w,
+    's,
+    (&'a Interaction, &'a ProfileOption, &'a mut BackgroundColor),
+    (Changed<Interaction>, With<Button>),
+>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EditMode {
+    Create,
+    Rename(String),
+}
+
+// Name being typed for a new or renamed profile
+#[derive(Resource, Default)]
+pub struct ProfileEdit {
+    pub mode: Option<EditMode>,
+    pub name: String,
+    // Profile waiting for a second press of its delete button
+    pub confirm_delete: Option<String>,
+}
+
+#[derive(Component)]
+pub struct ProfilesMenu;
+
+#[derive(Component)]
+pub struct ProfileList;
+
+#[derive(Component)]
+pub struct ProfileNameInput;
+
+#[derive(Component)]
+pub struct ProfileNameText;
+
+#[derive(Component, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ProfileOption {
+    Select(usize),
+    Rename(usize),
+    Delete(usize),
+    New,
+    Back,
+}
+
+pub struct ProfilePickerPlugin;
+
+impl Plugin for ProfilePickerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProfileEdit>()
+            .add_systems(Startup, pick_at_startup)
+            .add_systems(OnEnter(AppState::Profiles), setup)
+            .add_systems(
+                Update,
+                (handle_typing, handle_input, update_list, update_labels)
+                    .chain()
+                    .run_if(in_state(AppState::Profiles)),
+            )
+            .add_systems(OnExit(AppState::Profiles), cleanup);
+    }
+}
+
+// A shared machine asks who is playing before showing the menu
+fn pick_at_startup(profiles: Res<Profiles>, mut app_state: ResMut<NextState<AppState>>) {
+    if profiles.entries.len() > 1 {
+        app_state.set(AppState::Profiles);
+    }
+}
+
+fn setup(mut commands: Commands, mut edit: ResMut<ProfileEdit>, settings: Res<UserSettings>) {
+    let language = settings.language;
+    *edit = ProfileEdit::default();
+
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(MENU_BG_COLOR),
+        ProfilesMenu,
+        children![
+            (
+                Text(language.translate(Phrase::WhosPlaying).to_string()),
+                TextFont {
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(TEXT_COLOR),
+            ),
+            (
+                ProfileList,
+                Node {
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::vertical(Val::Px(10.0)),
+                    ..default()
+                },
+            ),
+            (
+                ProfileNameInput,
+                Node {
+                    width: Val::Percent(80.0),
+                    margin: UiRect::all(Val::Px(10.0)),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    display: Display::None,
+                    ..default()
+                },
+                BorderRadius::all(Val::Px(6.0)),
+                BackgroundColor(INPUT_BG_COLOR),
+                children![(
+                    ProfileNameText,
+                    Text::default(),
+                    TextFont {
+                        font_size: 22.0,
+                        ..default()
+                    },
+                    TextColor(TEXT_COLOR),
+                )],
+            ),
+            (
+                Node {
+                    width: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![
+                    create_button(
+                        ProfileOption::New,
+                        language.translate(Phrase::NewProfile),
+                        Val::Percent(40.0),
+                    ),
+                    create_button(
+                        ProfileOption::Back,
+                        language.translate(Phrase::Back),
+                        Val::Percent(40.0),
+                    ),
+                ],
+            ),
+        ],
+    ));
+}
+
+fn create_button(option: ProfileOption, label: &str, width: Val) -> impl Bundle {
+    (
+        Node {
+            width,
+            height: Val::Px(30.0),
+            margin: UiRect::all(Val::Px(4.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(BUTTON_COLOR_IDLE),
+        Button,
+        option,
+        children![(
+            Text(label.to_string()),
+            TextFont {
+                font_size: 16.0,
+                ..default()
+            },
+        )],
+    )
+}
+
+fn cleanup(
+    mut commands: Commands,
+    query: Query<Entity, With<ProfilesMenu>>,
+    mut edit: ResMut<ProfileEdit>,
+    mut focused: ResMut<FocusedButton>,
+) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+    if edit.mode.take().is_some() {
+        focused.locked = false;
+    }
+}
+
+fn start_edit(edit: &mut ProfileEdit, focused: &mut FocusedButton, mode: EditMode, name: String) {
+    edit.mode = Some(mode);
+    edit.name = name;
+    edit.confirm_delete = None;
+    // Arrow keys and confirm go to the name while it's typed
+    focused.locked = true;
+}
+
+fn handle_typing(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    actions: Res<ActionState>,
+    mut edit: ResMut<ProfileEdit>,
+    mut profiles: ResMut<Profiles>,
+    mut focused: ResMut<FocusedButton>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    // Back while typing only cancels the name
+    if actions.just_pressed(GameAction::Back) {
+        if edit.mode.take().is_some() {
+            focused.locked = false;
+        } else {
+            app_state.set(AppState::MainMenu);
+        }
+        return;
+    }
+
+    let Some(mode) = edit.mode.clone() else {
+        keyboard_events.clear();
+        return;
+    };
+
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Character(chars) => {
+                for c in chars.chars().filter(|c| c.is_ascii_alphanumeric()) {
+                    if edit.name.len() < MAX_NAME_LEN {
+                        edit.name.push(c);
+                    }
+                }
+            }
+            Key::Backspace => {
+                edit.name.pop();
+            }
+            _ => {}
+        }
+    }
+
+    if !actions.just_pressed(GameAction::Confirm) || edit.name.is_empty() {
+        return;
+    }
+
+    match mode {
+        EditMode::Create => {
+            // The new profile is the one that plays next
+            profiles.active = profiles.create(&edit.name);
+        }
+        EditMode::Rename(id) => {
+            profiles.rename(&id, &edit.name);
+        }
+    }
+    edit.mode = None;
+    focused.locked = false;
+}
+
+fn handle_input(
+    mut interaction_query: QueryButton,
+    mut profiles: ResMut<Profiles>,
+    mut edit: ResMut<ProfileEdit>,
+    mut focused: ResMut<FocusedButton>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, option, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                match option {
+                    ProfileOption::Select(index) => {
+                        profiles.active = profiles.entries[*index].id.clone();
+                        app_state.set(AppState::MainMenu);
+                    }
+                    ProfileOption::Rename(index) => {
+                        let entry = profiles.entries[*index].clone();
+                        start_edit(
+                            &mut edit,
+                            &mut focused,
+                            EditMode::Rename(entry.id),
+                            entry.name,
+                        );
+                    }
+                    ProfileOption::Delete(index) => {
+                        let id = profiles.entries[*index].id.clone();
+                        if edit.confirm_delete.as_ref() == Some(&id) {
+                            edit.confirm_delete = None;
+                            profiles.remove(&id);
+                        } else {
+                            edit.confirm_delete = Some(id);
+                        }
+                    }
+                    ProfileOption::New => {
+                        start_edit(&mut edit, &mut focused, EditMode::Create, String::new());
+                    }
+                    ProfileOption::Back => {
+                        app_state.set(AppState::MainMenu);
+                    }
+                }
+                *bg_color = BackgroundColor(BUTTON_COLOR_PRESSED);
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(BUTTON_COLOR_HOVER);
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(BUTTON_COLOR_IDLE);
+            }
+        }
+    }
+}
+
+// Rows are rebuilt whenever a profile is added, renamed or removed
+fn update_list(
+    mut commands: Commands,
+    profiles: Res<Profiles>,
+    settings: Res<UserSettings>,
+    list_query: Single<(Entity, Ref<ProfileList>)>,
+) {
+    let (list, marker) = list_query.into_inner();
+    if !profiles.is_changed() && !marker.is_added() {
+        return;
+    }
+    let language = settings.language;
+
+    commands.entity(list).despawn_related::<Children>();
+    commands.entity(list).with_children(|parent| {
+        for (index, entry) in profiles.entries.iter().enumerate() {
+            let label = if entry.id == profiles.active {
+                format!("> {} <", entry.name)
+            } else {
+                entry.name.clone()
+            };
+
+            let mut row = parent.spawn(Node {
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            });
+            row.with_children(|row| {
+                row.spawn(create_button(
+                    ProfileOption::Select(index),
+                    &label,
+                    Val::Percent(45.0),
+                ));
+                row.spawn(create_button(
+                    ProfileOption::Rename(index),
+                    language.translate(Phrase::Rename),
+                    Val::Percent(20.0),
+                ));
+                // There is always at least one profile
+                if profiles.entries.len() > 1 {
+                    row.spawn(create_button(
+                        ProfileOption::Delete(index),
+                        language.translate(Phrase::Delete),
+                        Val::Percent(20.0),
+                    ));
+                }
+            });
+        }
+    });
+}
+
+fn update_labels(
+    profiles: Res<Profiles>,
+    edit: Res<ProfileEdit>,
+    settings: Res<UserSettings>,
+    button_query: Query<(&ProfileOption, &Children)>,
+    mut text_query: Query<&mut Text, Without<ProfileNameText>>,
+    input_query: Single<&mut Node, With<ProfileNameInput>>,
+    name_query: Single<&mut Text, With<ProfileNameText>>,
+) {
+    if !edit.is_changed() && !profiles.is_changed() {
+        return;
+    }
+    let language = settings.language;
+
+    for (option, children) in &button_query {
+        let ProfileOption::Delete(index) = option else {
+            continue;
+        };
+        let confirming = profiles
+            .entries
+            .get(*index)
+            .is_some_and(|entry| edit.confirm_delete.as_ref() == Some(&entry.id));
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                let label = if confirming {
+                    Phrase::ConfirmDelete
+                } else {
+                    Phrase::Delete
+                };
+                text.0 = language.translate(label).to_string();
+            }
+        }
+    }
+
+    input_query.into_inner().display = if edit.mode.is_some() {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    name_query.into_inner().0 = match &edit.mode {
+        Some(EditMode::Create) => language.fill(Phrase::NewProfileName, &[&edit.name]),
+        Some(EditMode::Rename(_)) => language.fill(Phrase::RenameTo, &[&edit.name]),
+        None => String::new(),
+    };
+}
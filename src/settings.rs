@@ -1,57 +1,478 @@
-use bevy::prelude::*;
+use std::time::{Duration, Instant};
+
+use bevy::{
+    prelude::*,
+    window::{MonitorSelection, PresentMode, PrimaryWindow, WindowMode},
+};
 use bevy_flappy_macros::hex_to_color;
+use serde::{Deserialize, Serialize};
 
-use crate::game::AppState;
+use crate::game::{
+    AppState,
+    audio::AudioChannel,
+    course::DifficultyPreset,
+    ghost::Replay,
+    skins::{DEFAULT_SKIN, Skin, SkinRegistry},
+    sound_packs::{DEFAULT_SOUND_PACK, SoundPackRegistry},
+};
+use crate::input::{ActionState, GameAction, InputBindings};
+use crate::locale::{Language, Phrase};
+use crate::profile::{ActiveProfile, Profile};
+use crate::storage;
+use crate::ui::widgets::{Dropdown, Slider, Toggle, dropdown, slider, toggle};
 
 const MENU_BG_COLOR: Color = hex_to_color!("#e4ede6");
 const BUTTON_COLOR_IDLE: Color = hex_to_color!("#c3d8d2");
 const BUTTON_COLOR_HOVER: Color = hex_to_color!("#f4f5f4");
 const BUTTON_COLOR_PRESSED: Color = hex_to_color!("#c3d8d2");
+const TEXT_COLOR: Color = hex_to_color!("#2f3e46");
+
+pub const SETTINGS_FILE: &str = "settings.ron";
+const PREVIEW_FRAME_TIME: f32 = 0.15;
+// Up, mid, down, mid like the original flap cycle
+const PREVIEW_FRAME_ORDER: [usize; 4] = [0, 1, 2, 1];
+// Left and right move a volume slider by this much
+const VOLUME_STEP: f32 = 0.1;
+pub const FRAME_LIMITS: [Option<u32>; 5] = [None, Some(30), Some(60), Some(120), Some(144)];
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct AudioLevels {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+    pub muted: bool,
+    // How far sound effects are panned towards where they happen on screen
+    pub stereo_width: f32,
+}
+
+impl Default for AudioLevels {
+    fn default() -> Self {
+        Self {
+            master: 0.5,
+            music: 1.0,
+            sfx: 1.0,
+            muted: false,
+            stereo_width: 0.8,
+        }
+    }
+}
 
-#[derive(Resource)]
-pub struct Volume(pub f32);
+impl AudioLevels {
+    // Volume of a channel once the master level and mute are applied
+    pub fn channel(&self, channel: AudioChannel) -> f32 {
+        if self.muted {
+            return 0.0;
+        }
+        let level = match channel {
+            AudioChannel::Music => self.music,
+            AudioChannel::Sfx => self.sfx,
+        };
+        self.master * level
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct DisplaySettings {
+    pub fullscreen: bool,
+    pub vsync: bool,
+    // Frames per second, or no limit
+    pub frame_limit: Option<u32>,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            fullscreen: false,
+            vsync: true,
+            frame_limit: None,
+        }
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct UserSettings {
+    pub skin: String,
+    pub bindings: InputBindings,
+    // Replay raced as a ghost, if any
+    pub ghost: Option<String>,
+    // Rewards passing close to the gap centre
+    pub precision_scoring: bool,
+    pub audio: AudioLevels,
+    // Id of the sound pack the game sounds come from
+    pub sound_pack: String,
+    pub display: DisplaySettings,
+    // Preset of the randomly generated courses started from the menu
+    pub difficulty: DifficultyPreset,
+    pub language: Language,
+}
+
+impl Default for UserSettings {
+    fn default() -> Self {
+        Self {
+            skin: DEFAULT_SKIN.to_string(),
+            bindings: InputBindings::default(),
+            ghost: None,
+            precision_scoring: false,
+            audio: AudioLevels::default(),
+            sound_pack: DEFAULT_SOUND_PACK.to_string(),
+            display: DisplaySettings::default(),
+            difficulty: DifficultyPreset::default(),
+            language: Language::default(),
+        }
+    }
+}
+
+impl UserSettings {
+    pub fn load(profile: &str) -> Self {
+        storage::load(storage::profile_path(profile, SETTINGS_FILE)).unwrap_or_default()
+    }
+
+    pub fn save(&self, profile: &str) {
+        if let Err(err) = storage::save(storage::profile_path(profile, SETTINGS_FILE), self) {
+            warn!("Failed to save settings: {err}");
+        }
+    }
+}
 
 pub struct SettingsPlugin;
 
 #[derive(Component)]
 pub struct SettingsMenu;
 
+#[derive(Component)]
+pub struct GhostText;
+
+// Text that follows the chosen language
+#[derive(Component)]
+pub struct SettingLabel(pub Phrase);
+
+#[derive(Component)]
+pub struct SkinPreview {
+    pub frames: [Handle<Image>; 3],
+    pub timer: Timer,
+    pub step: usize,
+}
+
 #[derive(Component, Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum SettingsOption {
+    Skin,
+    Difficulty,
+    Language,
+    Ghost,
+    Precision,
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    StereoWidth,
+    SoundPack,
+    Mute,
+    Fullscreen,
+    Vsync,
+    FrameLimit,
+    Controls,
     Back,
 }
 
 impl Plugin for SettingsPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(Volume(0.5))
+        app.init_resource::<UserSettings>()
+            .init_resource::<SkinRegistry>()
+            .init_resource::<SoundPackRegistry>()
             .add_systems(OnEnter(AppState::Settings), setup)
-            .add_systems(Update, (handle_input).run_if(in_state(AppState::Settings)))
+            .add_systems(
+                Update,
+                (
+                    handle_input,
+                    apply_sliders,
+                    apply_toggles,
+                    apply_dropdowns,
+                    update_skin_preview,
+                    animate_skin_preview,
+                    update_ghost_label,
+                    update_labels,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Settings)),
+            )
+            .add_systems(
+                Update,
+                (sync_bindings, save_settings, apply_display_settings).chain(),
+            )
+            .add_systems(Last, limit_frame_rate)
             .add_systems(OnExit(AppState::Settings), cleanup);
     }
 }
 
-fn setup(mut commands: Commands) {
-    commands.spawn((
+fn setup(
+    mut commands: Commands,
+    settings: Res<UserSettings>,
+    registry: Res<SkinRegistry>,
+    sound_packs: Res<SoundPackRegistry>,
+    profile: Res<Profile>,
+) {
+    fn create_button(phrase: Phrase, option: SettingsOption) -> impl Bundle {
+        (
+            Node {
+                width: Val::Percent(30.0),
+                height: Val::Px(40.0),
+                margin: UiRect::all(Val::Px(10.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(BUTTON_COLOR_IDLE),
+            Button,
+            option,
+            children![(SettingLabel(phrase), Text::default())],
+        )
+    }
+
+    // Label on the left, widget on the right
+    fn setting_row(phrase: Phrase, widget: impl Bundle) -> impl Bundle {
+        (
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Px(36.0),
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            children![
+                (
+                    SettingLabel(phrase),
+                    Text::default(),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(TEXT_COLOR),
+                ),
+                (
+                    Node {
+                        width: Val::Percent(50.0),
+                        justify_content: JustifyContent::FlexEnd,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(6.0),
+                        ..default()
+                    },
+                    children![widget],
+                ),
+            ],
+        )
+    }
+
+    fn column(rows: impl Bundle) -> impl Bundle {
+        (
+            Node {
+                width: Val::Percent(44.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            rows,
+        )
+    }
+
+    let skins = unlocked_skins(&registry, &profile);
+    let skin = profile.skin(&settings);
+    let skin_picker = (
         Node {
             width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(6.0),
+            ..default()
+        },
+        children![
+            (
+                Node {
+                    width: Val::Px(34.0),
+                    height: Val::Px(24.0),
+                    flex_shrink: 0.0,
+                    ..default()
+                },
+                ImageNode::default(),
+                SkinPreview {
+                    frames: default(),
+                    timer: Timer::from_seconds(PREVIEW_FRAME_TIME, TimerMode::Repeating),
+                    step: 0,
+                },
+            ),
+            (
+                dropdown(
+                    skins.iter().map(|skin| skin.name.clone()).collect(),
+                    skins.iter().position(|s| s.id == skin).unwrap_or(0),
+                ),
+                SettingsOption::Skin,
+            ),
+        ],
+    );
+    let ghost_button = (
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Px(24.0),
             justify_content: JustifyContent::Center,
             align_items: AlignItems::Center,
+            overflow: Overflow::clip(),
             ..default()
         },
-        BackgroundColor(MENU_BG_COLOR),
-        SettingsMenu,
+        BackgroundColor(BUTTON_COLOR_IDLE),
+        Button,
+        SettingsOption::Ghost,
         children![(
-            Node {
-                width: Val::Percent(50.0),
-                height: Val::Percent(30.0),
+            GhostText,
+            Text::default(),
+            TextFont {
+                font_size: 14.0,
                 ..default()
             },
-            BackgroundColor(BUTTON_COLOR_IDLE),
-            Button,
-            SettingsOption::Back,
-            children![Text("Back".to_string()),],
         )],
+    );
+    let audio = settings.audio;
+    let display = settings.display;
+
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(MENU_BG_COLOR),
+        SettingsMenu,
+        children![
+            (
+                SettingLabel(Phrase::Settings),
+                Text::default(),
+                TextFont {
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(TEXT_COLOR),
+            ),
+            (
+                Node {
+                    width: Val::Percent(100.0),
+                    justify_content: JustifyContent::SpaceEvenly,
+                    margin: UiRect::vertical(Val::Px(20.0)),
+                    ..default()
+                },
+                children![
+                    column(children![
+                        setting_row(Phrase::Skin, skin_picker),
+                        setting_row(
+                            Phrase::Difficulty,
+                            (
+                                dropdown(
+                                    difficulty_names(settings.language),
+                                    index_of(&DifficultyPreset::ALL, &settings.difficulty),
+                                ),
+                                SettingsOption::Difficulty,
+                            ),
+                        ),
+                        setting_row(
+                            Phrase::Language,
+                            (
+                                dropdown(
+                                    Language::ALL
+                                        .iter()
+                                        .map(|language| language.name().to_string())
+                                        .collect(),
+                                    index_of(&Language::ALL, &settings.language),
+                                ),
+                                SettingsOption::Language,
+                            ),
+                        ),
+                        setting_row(Phrase::Ghost, ghost_button),
+                        setting_row(
+                            Phrase::PrecisionScoring,
+                            (
+                                toggle(settings.precision_scoring),
+                                SettingsOption::Precision,
+                            ),
+                        ),
+                    ]),
+                    column(children![
+                        setting_row(
+                            Phrase::MasterVolume,
+                            (
+                                slider(audio.master, VOLUME_STEP),
+                                SettingsOption::MasterVolume,
+                            ),
+                        ),
+                        setting_row(
+                            Phrase::MusicVolume,
+                            (
+                                slider(audio.music, VOLUME_STEP),
+                                SettingsOption::MusicVolume,
+                            ),
+                        ),
+                        setting_row(
+                            Phrase::SfxVolume,
+                            (slider(audio.sfx, VOLUME_STEP), SettingsOption::SfxVolume),
+                        ),
+                        setting_row(
+                            Phrase::StereoWidth,
+                            (
+                                slider(audio.stereo_width, VOLUME_STEP),
+                                SettingsOption::StereoWidth,
+                            ),
+                        ),
+                        setting_row(
+                            Phrase::SoundPack,
+                            (
+                                dropdown(
+                                    sound_packs
+                                        .packs
+                                        .iter()
+                                        .map(|pack| pack.name.clone())
+                                        .collect(),
+                                    sound_packs
+                                        .packs
+                                        .iter()
+                                        .position(|pack| pack.id == settings.sound_pack)
+                                        .unwrap_or(0),
+                                ),
+                                SettingsOption::SoundPack,
+                            ),
+                        ),
+                        setting_row(Phrase::Mute, (toggle(audio.muted), SettingsOption::Mute)),
+                        setting_row(
+                            Phrase::Fullscreen,
+                            (toggle(display.fullscreen), SettingsOption::Fullscreen),
+                        ),
+                        setting_row(
+                            Phrase::Vsync,
+                            (toggle(display.vsync), SettingsOption::Vsync)
+                        ),
+                        setting_row(
+                            Phrase::FrameLimit,
+                            (
+                                dropdown(
+                                    frame_limit_names(settings.language),
+                                    index_of(&FRAME_LIMITS, &display.frame_limit),
+                                ),
+                                SettingsOption::FrameLimit,
+                            ),
+                        ),
+                    ]),
+                ],
+            ),
+            (
+                Node {
+                    width: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![
+                    create_button(Phrase::Controls, SettingsOption::Controls),
+                    create_button(Phrase::Back, SettingsOption::Back),
+                ],
+            ),
+        ],
     ));
 }
 
@@ -68,14 +489,32 @@ fn cleanup(mut commands: Commands, query: Query<Entity, With<SettingsMenu>>) {
     }
 }
 
-fn handle_input(mut interaction_query: QueryButton, mut app_state: ResMut<NextState<AppState>>) {
+fn handle_input(
+    mut interaction_query: QueryButton,
+    actions: Res<ActionState>,
+    mut app_state: ResMut<NextState<AppState>>,
+    mut settings: ResMut<UserSettings>,
+    active: Res<ActiveProfile>,
+) {
+    if actions.just_pressed(GameAction::Back) {
+        app_state.set(AppState::MainMenu);
+    }
+
     for (interaction, button_type, mut bg_color) in &mut interaction_query {
         match *interaction {
             Interaction::Pressed => {
                 match button_type {
+                    SettingsOption::Ghost => {
+                        settings.ghost = next_ghost(&active.0, settings.ghost.as_deref());
+                    }
+                    SettingsOption::Controls => {
+                        app_state.set(AppState::Controls);
+                    }
                     SettingsOption::Back => {
                         app_state.set(AppState::MainMenu);
                     }
+                    // Sliders, toggles and dropdowns handle their own presses
+                    _ => {}
                 }
                 *bg_color = BackgroundColor(BUTTON_COLOR_PRESSED);
             }
@@ -88,3 +527,256 @@ fn handle_input(mut interaction_query: QueryButton, mut app_state: ResMut<NextSt
         }
     }
 }
+
+fn apply_sliders(
+    mut settings: ResMut<UserSettings>,
+    slider_query: Query<(&SettingsOption, &Slider), Changed<Slider>>,
+) {
+    for (option, slider) in &slider_query {
+        let mut level = match option {
+            SettingsOption::MasterVolume => {
+                settings.reborrow().map_unchanged(|s| &mut s.audio.master)
+            }
+            SettingsOption::MusicVolume => {
+                settings.reborrow().map_unchanged(|s| &mut s.audio.music)
+            }
+            SettingsOption::SfxVolume => settings.reborrow().map_unchanged(|s| &mut s.audio.sfx),
+            SettingsOption::StereoWidth => settings
+                .reborrow()
+                .map_unchanged(|s| &mut s.audio.stereo_width),
+            _ => continue,
+        };
+        level.set_if_neq(slider.value);
+    }
+}
+
+fn apply_toggles(
+    mut settings: ResMut<UserSettings>,
+    toggle_query: Query<(&SettingsOption, &Toggle), Changed<Toggle>>,
+) {
+    for (option, toggle) in &toggle_query {
+        let mut value = match option {
+            SettingsOption::Precision => settings
+                .reborrow()
+                .map_unchanged(|s| &mut s.precision_scoring),
+            SettingsOption::Mute => settings.reborrow().map_unchanged(|s| &mut s.audio.muted),
+            SettingsOption::Fullscreen => settings
+                .reborrow()
+                .map_unchanged(|s| &mut s.display.fullscreen),
+            SettingsOption::Vsync => settings.reborrow().map_unchanged(|s| &mut s.display.vsync),
+            _ => continue,
+        };
+        value.set_if_neq(toggle.on);
+    }
+}
+
+fn apply_dropdowns(
+    mut settings: ResMut<UserSettings>,
+    registry: Res<SkinRegistry>,
+    sound_packs: Res<SoundPackRegistry>,
+    profile: Res<Profile>,
+    dropdown_query: Query<(&SettingsOption, &Dropdown), Changed<Dropdown>>,
+) {
+    for (option, dropdown) in &dropdown_query {
+        let index = dropdown.selected;
+        match option {
+            SettingsOption::Skin => {
+                if let Some(skin) = unlocked_skins(&registry, &profile).get(index) {
+                    // The default skin stands in for a locked one, which is kept until changed
+                    if profile.skin(&settings) != skin.id {
+                        settings.skin = skin.id.clone();
+                    }
+                }
+            }
+            SettingsOption::SoundPack => {
+                if let Some(pack) = sound_packs.packs.get(index)
+                    && settings.sound_pack != pack.id
+                {
+                    settings.sound_pack = pack.id.clone();
+                }
+            }
+            SettingsOption::Difficulty => {
+                settings
+                    .reborrow()
+                    .map_unchanged(|s| &mut s.difficulty)
+                    .set_if_neq(DifficultyPreset::ALL[index]);
+            }
+            SettingsOption::Language => {
+                settings
+                    .reborrow()
+                    .map_unchanged(|s| &mut s.language)
+                    .set_if_neq(Language::ALL[index]);
+            }
+            SettingsOption::FrameLimit => {
+                settings
+                    .reborrow()
+                    .map_unchanged(|s| &mut s.display.frame_limit)
+                    .set_if_neq(FRAME_LIMITS[index]);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn update_skin_preview(
+    asset_server: Res<AssetServer>,
+    settings: Res<UserSettings>,
+    profile: Res<Profile>,
+    registry: Res<SkinRegistry>,
+    mut preview_query: Query<Mut<SkinPreview>>,
+) {
+    for mut preview in &mut preview_query {
+        if !settings.is_changed() && !preview.is_added() {
+            continue;
+        }
+        let skin = registry.get(profile.skin(&settings));
+        preview.frames = skin.frames.clone().map(|path| asset_server.load(path));
+    }
+}
+
+fn animate_skin_preview(time: Res<Time>, mut query: Query<(&mut ImageNode, &mut SkinPreview)>) {
+    for (mut image, mut preview) in &mut query {
+        preview.timer.tick(time.delta());
+        if preview.timer.just_finished() {
+            preview.step = (preview.step + 1) % PREVIEW_FRAME_ORDER.len();
+        }
+
+        let frame = &preview.frames[PREVIEW_FRAME_ORDER[preview.step]];
+        if image.image != *frame {
+            image.image = frame.clone();
+        }
+    }
+}
+
+// Skins that still have to be bought in the shop aren't offered
+fn unlocked_skins<'a>(registry: &'a SkinRegistry, profile: &Profile) -> Vec<&'a Skin> {
+    registry
+        .skins
+        .iter()
+        .filter(|skin| profile.skin_unlocked(&skin.id))
+        .collect()
+}
+
+fn index_of<T: PartialEq>(all: &[T], value: &T) -> usize {
+    all.iter().position(|item| item == value).unwrap_or(0)
+}
+
+fn difficulty_names(language: Language) -> Vec<String> {
+    DifficultyPreset::ALL
+        .iter()
+        .map(|preset| language.translate(preset.phrase()).to_string())
+        .collect()
+}
+
+fn frame_limit_names(language: Language) -> Vec<String> {
+    FRAME_LIMITS
+        .iter()
+        .map(|limit| match limit {
+            Some(fps) => format!("{fps} FPS"),
+            None => language.translate(Phrase::Off).to_string(),
+        })
+        .collect()
+}
+
+// Cycles through no ghost and every saved replay
+fn next_ghost(profile: &str, current: Option<&str>) -> Option<String> {
+    let replays = Replay::list(profile);
+    let next = match current.and_then(|name| replays.iter().position(|replay| replay == name)) {
+        Some(index) => index + 1,
+        None if current.is_some() => replays.len(),
+        None => 0,
+    };
+    replays.get(next).cloned()
+}
+
+fn update_ghost_label(
+    settings: Res<UserSettings>,
+    ghost_query: Single<(&mut Text, Ref<GhostText>)>,
+) {
+    let (mut text, marker) = ghost_query.into_inner();
+    if !settings.is_changed() && !marker.is_added() {
+        return;
+    }
+
+    text.0 = match &settings.ghost {
+        Some(name) => Replay::display_name(name, settings.language),
+        None => settings.language.translate(Phrase::Off).to_string(),
+    };
+}
+
+fn update_labels(
+    settings: Res<UserSettings>,
+    mut label_query: Query<(&mut Text, Ref<SettingLabel>)>,
+    mut dropdown_query: Query<(&SettingsOption, &mut Dropdown)>,
+) {
+    for (mut text, label) in &mut label_query {
+        if settings.is_changed() || label.is_added() {
+            text.0 = settings.language.translate(label.0).to_string();
+        }
+    }
+
+    if !settings.is_changed() {
+        return;
+    }
+    // Choices named in words follow the language too
+    for (option, mut dropdown) in &mut dropdown_query {
+        let names = match option {
+            SettingsOption::Difficulty => difficulty_names(settings.language),
+            SettingsOption::FrameLimit => frame_limit_names(settings.language),
+            _ => continue,
+        };
+        if dropdown.options != names {
+            dropdown.options = names;
+        }
+    }
+}
+
+// Sleeps off the rest of the frame when a frame limit is set
+fn limit_frame_rate(settings: Res<UserSettings>, mut frame_start: Local<Option<Instant>>) {
+    if let (Some(fps), Some(start)) = (settings.display.frame_limit, *frame_start) {
+        let frame_time = Duration::from_secs_f64(1.0 / f64::from(fps));
+        if let Some(remaining) = frame_time.checked_sub(start.elapsed()) {
+            std::thread::sleep(remaining);
+        }
+    }
+    *frame_start = Some(Instant::now());
+}
+
+fn apply_display_settings(
+    settings: Res<UserSettings>,
+    window: Single<&mut Window, With<PrimaryWindow>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    let mut window = window.into_inner();
+    let mode = if settings.display.fullscreen {
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+    } else {
+        WindowMode::Windowed
+    };
+    let present_mode = if settings.display.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    if window.mode != mode {
+        window.mode = mode;
+    }
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
+
+fn sync_bindings(bindings: Res<InputBindings>, mut settings: ResMut<UserSettings>) {
+    if bindings.is_changed() && !bindings.is_added() {
+        settings.bindings = bindings.clone();
+    }
+}
+
+fn save_settings(settings: Res<UserSettings>, active: Res<ActiveProfile>) {
+    if settings.is_changed() && !settings.is_added() {
+        settings.save(&active.0);
+    }
+}
@@ -0,0 +1,326 @@
+use bevy::prelude::*;
+use bevy_flappy_macros::hex_to_color;
+
+use crate::game::AppState;
+use crate::input::{ActionState, GameAction};
+use crate::locale::Phrase;
+use crate::profile::Profile;
+use crate::settings::UserSettings;
+
+const MENU_BG_COLOR: Color = hex_to_color!("#e4ede6");
+const BUTTON_COLOR_IDLE: Color = hex_to_color!("#c3d8d2");
+const BUTTON_COLOR_HOVER: Color = hex_to_color!("#f4f5f4");
+const BUTTON_COLOR_PRESSED: Color = hex_to_color!("#c3d8d2");
+const TEXT_COLOR: Color = hex_to_color!("#2f3e46");
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ItemKind {
+    Skin,
+    Background,
+    Trail,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ShopItem {
+    // Skin, background or trail id the item unlocks
+    pub id: &'static str,
+    pub name: Phrase,
+    pub kind: ItemKind,
+    pub price: u32,
+}
+
+pub const SHOP_ITEMS: [ShopItem; 6] = [
+    ShopItem {
+        id: "red",
+        name: Phrase::RedBird,
+        kind: ItemKind::Skin,
+        price: 50,
+    },
+    ShopItem {
+        id: "blue",
+        name: Phrase::BlueBird,
+        kind: ItemKind::Skin,
+        price: 50,
+    },
+    ShopItem {
+        id: "day",
+        name: Phrase::DaySky,
+        kind: ItemKind::Background,
+        price: 0,
+    },
+    ShopItem {
+        id: "night",
+        name: Phrase::NightSky,
+        kind: ItemKind::Background,
+        price: 100,
+    },
+    ShopItem {
+        id: "sparkle",
+        name: Phrase::SparkleTrail,
+        kind: ItemKind::Trail,
+        price: 75,
+    },
+    ShopItem {
+        id: "rainbow",
+        name: Phrase::RainbowTrail,
+        kind: ItemKind::Trail,
+        price: 150,
+    },
+];
+
+impl ShopItem {
+    pub fn equipped(&self, profile: &Profile, settings: &UserSettings) -> bool {
+        match self.kind {
+            ItemKind::Skin => settings.skin == self.id,
+            ItemKind::Background => profile.background == self.id,
+            ItemKind::Trail => profile.trail.as_deref() == Some(self.id),
+        }
+    }
+}
+
+type QueryButton<'w, 's, 'a> = Query<
+    'w,
+    's,
+    (&'a Interaction, &'a ShopOption, &'a mut BackgroundColor),
+    (Changed<Interaction>, With<Button>),
+>;
+
+#[derive(Resource, Default)]
+pub struct ShopStatus(pub String);
+
+#[derive(Component)]
+pub struct ShopMenu;
+
+#[derive(Component)]
+pub struct ShopCoinsText;
+
+#[derive(Component)]
+pub struct ShopStatusText;
+
+#[derive(Component, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ShopOption {
+    Item(usize),
+    Back,
+}
+
+pub struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShopStatus>()
+            .add_systems(OnEnter(AppState::Shop), setup)
+            .add_systems(
+                Update,
+                (handle_input, update_labels)
+                    .chain()
+                    .run_if(in_state(AppState::Shop)),
+            )
+            .add_systems(OnExit(AppState::Shop), cleanup);
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<UserSettings>,
+    mut status: ResMut<ShopStatus>,
+) {
+    fn create_button(option: ShopOption, width: Val) -> impl Bundle {
+        (
+            Node {
+                width,
+                height: Val::Px(36.0),
+                margin: UiRect::all(Val::Px(4.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(BUTTON_COLOR_IDLE),
+            Button,
+            option,
+            children![(
+                Text::default(),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+            )],
+        )
+    }
+
+    status.0.clear();
+
+    let root = commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(MENU_BG_COLOR),
+            ShopMenu,
+        ))
+        .id();
+
+    commands.entity(root).with_children(|parent| {
+        parent.spawn((
+            Text(settings.language.translate(Phrase::Shop).to_string()),
+            TextFont {
+                font_size: 32.0,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+        ));
+        parent.spawn((
+            Node {
+                align_items: AlignItems::Center,
+                margin: UiRect::bottom(Val::Px(10.0)),
+                column_gap: Val::Px(6.0),
+                ..default()
+            },
+            children![
+                (
+                    Node {
+                        width: Val::Px(20.0),
+                        height: Val::Px(20.0),
+                        ..default()
+                    },
+                    ImageNode::new(asset_server.load("sprites/coin.png")),
+                ),
+                (ShopCoinsText, Text::default(), TextColor(TEXT_COLOR)),
+            ],
+        ));
+
+        for index in 0..SHOP_ITEMS.len() {
+            parent.spawn(create_button(ShopOption::Item(index), Val::Percent(70.0)));
+        }
+
+        parent.spawn((
+            ShopStatusText,
+            Text::default(),
+            TextFont {
+                font_size: 16.0,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+        ));
+        parent.spawn(create_button(ShopOption::Back, Val::Percent(40.0)));
+    });
+}
+
+fn cleanup(mut commands: Commands, query: Query<Entity, With<ShopMenu>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn handle_input(
+    mut interaction_query: QueryButton,
+    actions: Res<ActionState>,
+    mut profile: ResMut<Profile>,
+    mut settings: ResMut<UserSettings>,
+    mut status: ResMut<ShopStatus>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    if actions.just_pressed(GameAction::Back) {
+        app_state.set(AppState::MainMenu);
+    }
+
+    for (interaction, option, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                match option {
+                    ShopOption::Item(index) => {
+                        status.0 = select_item(&SHOP_ITEMS[*index], &mut profile, &mut settings);
+                    }
+                    ShopOption::Back => {
+                        app_state.set(AppState::MainMenu);
+                    }
+                }
+                *bg_color = BackgroundColor(BUTTON_COLOR_PRESSED);
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(BUTTON_COLOR_HOVER);
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(BUTTON_COLOR_IDLE);
+            }
+        }
+    }
+}
+
+// Buys the item if needed, then equips it. Selecting an equipped trail takes it off.
+fn select_item(item: &ShopItem, profile: &mut Profile, settings: &mut UserSettings) -> String {
+    let language = settings.language;
+    let name = language.translate(item.name);
+    let mut message = String::new();
+    if !profile.owns(item) {
+        if profile.coins < item.price {
+            return language.fill(Phrase::CoinsNeeded, &[&(item.price - profile.coins)]);
+        }
+        profile.coins -= item.price;
+        profile.unlocked.push(item.id.to_string());
+        message = language.fill(Phrase::Bought, &[&name]) + " ";
+    }
+
+    match item.kind {
+        ItemKind::Skin => settings.skin = item.id.to_string(),
+        ItemKind::Background => profile.background = item.id.to_string(),
+        ItemKind::Trail if profile.trail.as_deref() == Some(item.id) => {
+            profile.trail = None;
+            return language.fill(Phrase::TookOff, &[&name]);
+        }
+        ItemKind::Trail => profile.trail = Some(item.id.to_string()),
+    }
+    message + &language.fill(Phrase::ItemEquipped, &[&name])
+}
+
+#[allow(clippy::type_complexity)]
+fn update_labels(
+    profile: Res<Profile>,
+    settings: Res<UserSettings>,
+    status: Res<ShopStatus>,
+    button_query: Query<(&ShopOption, &Children)>,
+    mut text_query: Query<&mut Text, (Without<ShopCoinsText>, Without<ShopStatusText>)>,
+    coins_query: Single<(&mut Text, Ref<ShopCoinsText>), Without<ShopStatusText>>,
+    status_query: Single<&mut Text, With<ShopStatusText>>,
+) {
+    let (mut coins_text, coins_marker) = coins_query.into_inner();
+    if !profile.is_changed()
+        && !settings.is_changed()
+        && !status.is_changed()
+        && !coins_marker.is_added()
+    {
+        return;
+    }
+
+    coins_text.0 = profile.coins.to_string();
+    status_query.into_inner().0 = status.0.clone();
+
+    let language = settings.language;
+    for (option, children) in &button_query {
+        let label = match option {
+            ShopOption::Item(index) => {
+                let item = &SHOP_ITEMS[*index];
+                let state = if item.equipped(&profile, &settings) {
+                    language.translate(Phrase::Equipped).to_string()
+                } else if profile.owns(item) {
+                    language.translate(Phrase::Owned).to_string()
+                } else {
+                    language.fill(Phrase::Coins, &[&item.price])
+                };
+                format!("{}  -  {}", language.translate(item.name), state)
+            }
+            ShopOption::Back => language.translate(Phrase::Back).to_string(),
+        };
+
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0.clone_from(&label);
+            }
+        }
+    }
+}
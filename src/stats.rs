@@ -0,0 +1,514 @@
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use bevy_flappy_macros::hex_to_color;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    AppState, PlayPhase, Score,
+    coins::RunCoins,
+    course::{Course, DifficultyPreset},
+    events::{DeathCause, GameplayEvent},
+    level::ActiveLevel,
+};
+use crate::input::{ActionState, GameAction};
+use crate::locale::Phrase;
+use crate::profile::ActiveProfile;
+use crate::settings::UserSettings;
+use crate::storage;
+
+pub const STATS_FILE: &str = "stats.ron";
+// Points covered by each bar of the score histogram, the last bar takes everything above
+pub const HISTOGRAM_BUCKET_SIZE: u32 = 5;
+pub const HISTOGRAM_BUCKETS: usize = 10;
+const HISTOGRAM_BAR_WIDTH: f32 = 120.0;
+const SECONDS_PER_DAY: u64 = 86_400;
+
+const MENU_BG_COLOR: Color = hex_to_color!("#e4ede6");
+const BUTTON_COLOR_IDLE: Color = hex_to_color!("#c3d8d2");
+const BUTTON_COLOR_HOVER: Color = hex_to_color!("#f4f5f4");
+const BUTTON_COLOR_PRESSED: Color = hex_to_color!("#c3d8d2");
+const TEXT_COLOR: Color = hex_to_color!("#2f3e46");
+const BAR_COLOR: Color = hex_to_color!("#52796f");
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct DeathCounts {
+    pub top_pipe: u32,
+    pub bottom_pipe: u32,
+    pub ground: u32,
+}
+
+impl DeathCounts {
+    pub fn add(&mut self, cause: DeathCause) {
+        match cause {
+            DeathCause::TopPipe => self.top_pipe += 1,
+            DeathCause::BottomPipe => self.bottom_pipe += 1,
+            DeathCause::Ground => self.ground += 1,
+        }
+    }
+
+    fn merge(&mut self, other: &DeathCounts) {
+        self.top_pipe += other.top_pipe;
+        self.bottom_pipe += other.bottom_pipe;
+        self.ground += other.ground;
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct Stats {
+    pub games_played: u32,
+    pub flaps: u64,
+    pub pipes_passed: u64,
+    // Seconds spent flying, pauses excluded
+    pub time_played: f64,
+    pub coins: u64,
+    pub deaths: DeathCounts,
+    // Days since the Unix epoch of the latest game, for the daily streak
+    pub last_played_day: Option<u64>,
+    pub streak: u32,
+    pub best_streak: u32,
+    // Runs per score bucket on generated courses
+    pub histograms: BTreeMap<DifficultyPreset, Vec<u32>>,
+}
+
+impl Stats {
+    pub fn load(profile: &str) -> Self {
+        storage::load(storage::profile_path(profile, STATS_FILE)).unwrap_or_default()
+    }
+
+    pub fn save(&self, profile: &str) {
+        if let Err(err) = storage::save(storage::profile_path(profile, STATS_FILE), self) {
+            warn!("Failed to save stats: {err}");
+        }
+    }
+
+    // Counts consecutive days with at least one game
+    fn played_on(&mut self, day: u64) {
+        self.streak = match self.last_played_day {
+            Some(last) if last == day => self.streak,
+            Some(last) if last + 1 == day => self.streak + 1,
+            _ => 1,
+        };
+        self.best_streak = self.best_streak.max(self.streak);
+        self.last_played_day = Some(day);
+    }
+
+    fn add_score(&mut self, difficulty: DifficultyPreset, score: u32) {
+        let histogram = self
+            .histograms
+            .entry(difficulty)
+            .or_insert_with(|| vec![0; HISTOGRAM_BUCKETS]);
+        histogram.resize(HISTOGRAM_BUCKETS, 0);
+
+        let bucket = (score / HISTOGRAM_BUCKET_SIZE) as usize;
+        histogram[bucket.min(HISTOGRAM_BUCKETS - 1)] += 1;
+    }
+
+    pub fn histogram(&self, difficulty: DifficultyPreset) -> Vec<u32> {
+        let mut histogram = self
+            .histograms
+            .get(&difficulty)
+            .cloned()
+            .unwrap_or_default();
+        histogram.resize(HISTOGRAM_BUCKETS, 0);
+        histogram
+    }
+}
+
+// Totals of the run in progress, added to the lifetime stats when it ends
+#[derive(Resource, Default)]
+pub struct RunStats {
+    pub flaps: u64,
+    pub pipes_passed: u64,
+    pub time_played: f64,
+    pub deaths: DeathCounts,
+    // Cause and place of the death that ended the run
+    pub last_death: Option<(DeathCause, Vec2)>,
+}
+
+// Difficulty whose histogram the stats screen shows
+#[derive(Resource, Default)]
+pub struct StatsView {
+    pub difficulty: DifficultyPreset,
+}
+
+type QueryButton<'w, 's, 'a> = Query<
+    'w,
+    's,
+    (&'a Interaction, &'a StatsOption, &'a mut BackgroundColor),
+    (Changed<Interaction>, With<Button>),
+>;
+
+#[derive(Component)]
+pub struct StatsMenu;
+
+#[derive(Component)]
+pub struct HistogramList;
+
+#[derive(Component, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum StatsOption {
+    Difficulty,
+    Achievements,
+    Back,
+}
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Stats>()
+            .init_resource::<RunStats>()
+            .init_resource::<StatsView>()
+            .add_systems(OnEnter(AppState::InGame), reset_run_stats)
+            // After every gameplay system has written its events for the frame
+            .add_systems(
+                PostUpdate,
+                track_gameplay_events.run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(Update, track_play_time.run_if(in_state(PlayPhase::Playing)))
+            .add_systems(OnEnter(AppState::GameOver), record_run_stats)
+            .add_systems(OnEnter(AppState::Stats), setup)
+            .add_systems(
+                Update,
+                (handle_input, update_histogram)
+                    .chain()
+                    .run_if(in_state(AppState::Stats)),
+            )
+            .add_systems(OnExit(AppState::Stats), cleanup)
+            .add_systems(Update, save_stats);
+    }
+}
+
+fn save_stats(stats: Res<Stats>, active: Res<ActiveProfile>) {
+    if stats.is_changed() && !stats.is_added() {
+        stats.save(&active.0);
+    }
+}
+
+fn reset_run_stats(mut run_stats: ResMut<RunStats>) {
+    *run_stats = RunStats::default();
+}
+
+fn track_gameplay_events(
+    mut gameplay_events: EventReader<GameplayEvent>,
+    mut run_stats: ResMut<RunStats>,
+) {
+    for event in gameplay_events.read() {
+        match event {
+            GameplayEvent::Flap { .. } => run_stats.flaps += 1,
+            GameplayEvent::PipePassed { .. } => run_stats.pipes_passed += 1,
+            GameplayEvent::NearMiss { .. } => {}
+            GameplayEvent::Death {
+                cause, position, ..
+            } => {
+                run_stats.deaths.add(*cause);
+                run_stats.last_death = Some((*cause, *position));
+            }
+        }
+    }
+}
+
+fn track_play_time(time: Res<Time>, mut run_stats: ResMut<RunStats>) {
+    run_stats.time_played += time.delta_secs_f64();
+}
+
+fn record_run_stats(
+    run_stats: Res<RunStats>,
+    run_coins: Res<RunCoins>,
+    score: Res<Score>,
+    course: Res<Course>,
+    active_level: Res<ActiveLevel>,
+    settings: Res<UserSettings>,
+    mut stats: ResMut<Stats>,
+) {
+    // Test-plays from the editor don't count
+    if active_level.playtest {
+        return;
+    }
+
+    stats.games_played += 1;
+    stats.flaps += run_stats.flaps;
+    stats.pipes_passed += run_stats.pipes_passed;
+    stats.time_played += run_stats.time_played;
+    stats.coins += u64::from(run_coins.0);
+    stats.deaths.merge(&run_stats.deaths);
+
+    let day = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() / SECONDS_PER_DAY);
+    stats.played_on(day);
+
+    // Precision points would skew the histogram of pipe counts
+    if active_level.level.is_none() && !settings.precision_scoring {
+        stats.add_score(course.settings.preset, score.0);
+    }
+}
+
+fn setup(mut commands: Commands, stats: Res<Stats>, settings: Res<UserSettings>) {
+    let language = settings.language;
+    let deaths = &stats.deaths;
+    let lines = [
+        language.fill(Phrase::GamesPlayed, &[&stats.games_played]),
+        language.fill(Phrase::Flaps, &[&stats.flaps]),
+        language.fill(Phrase::PipesPassed, &[&stats.pipes_passed]),
+        language.fill(Phrase::TimePlayed, &[&format_duration(stats.time_played)]),
+        language.fill(Phrase::CoinsCollected, &[&stats.coins]),
+        language.fill(Phrase::TopPipeDeaths, &[&deaths.top_pipe]),
+        language.fill(Phrase::BottomPipeDeaths, &[&deaths.bottom_pipe]),
+        language.fill(Phrase::GroundDeaths, &[&deaths.ground]),
+        language.fill(Phrase::DailyStreak, &[&stats.streak]),
+        language.fill(Phrase::BestDailyStreak, &[&stats.best_streak]),
+    ];
+
+    let root = commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(MENU_BG_COLOR),
+            StatsMenu,
+        ))
+        .id();
+
+    commands.entity(root).with_children(|parent| {
+        parent.spawn((
+            Text(language.translate(Phrase::Stats).to_string()),
+            TextFont {
+                font_size: 32.0,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+        ));
+
+        parent
+            .spawn(Node {
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::SpaceEvenly,
+                margin: UiRect::vertical(Val::Px(10.0)),
+                ..default()
+            })
+            .with_children(|columns| {
+                // Lifetime totals
+                columns
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(4.0),
+                        ..default()
+                    })
+                    .with_children(|column| {
+                        for line in lines {
+                            column.spawn((
+                                Text(line),
+                                TextFont {
+                                    font_size: 16.0,
+                                    ..default()
+                                },
+                                TextColor(TEXT_COLOR),
+                            ));
+                        }
+                    });
+
+                // Score histogram of one difficulty
+                columns.spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    children![
+                        create_button(StatsOption::Difficulty, String::new()),
+                        (
+                            HistogramList,
+                            Node {
+                                flex_direction: FlexDirection::Column,
+                                row_gap: Val::Px(2.0),
+                                ..default()
+                            },
+                        ),
+                    ],
+                ));
+            });
+
+        parent.spawn((
+            Node {
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            children![
+                create_button(StatsOption::Back, language.translate(Phrase::Back).to_string()),
+                create_button(
+                    StatsOption::Achievements,
+                    language.translate(Phrase::Achievements).to_string(),
+                ),
+            ],
+        ));
+    });
+}
+
+fn create_button(option: StatsOption, label: String) -> impl Bundle {
+    (
+        Node {
+            width: Val::Px(180.0),
+            height: Val::Px(30.0),
+            margin: UiRect::all(Val::Px(4.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(BUTTON_COLOR_IDLE),
+        Button,
+        option,
+        children![(
+            Text(label),
+            TextFont {
+                font_size: 16.0,
+                ..default()
+            },
+        )],
+    )
+}
+
+fn cleanup(mut commands: Commands, query: Query<Entity, With<StatsMenu>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn handle_input(
+    mut interaction_query: QueryButton,
+    actions: Res<ActionState>,
+    mut view: ResMut<StatsView>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    if actions.just_pressed(GameAction::Back) {
+        app_state.set(AppState::MainMenu);
+    }
+
+    for (interaction, option, mut bg_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                match option {
+                    StatsOption::Difficulty => {
+                        let index = DifficultyPreset::ALL
+                            .iter()
+                            .position(|preset| *preset == view.difficulty)
+                            .unwrap_or(0);
+                        view.difficulty =
+                            DifficultyPreset::ALL[(index + 1) % DifficultyPreset::ALL.len()];
+                    }
+                    StatsOption::Achievements => {
+                        app_state.set(AppState::Achievements);
+                    }
+                    StatsOption::Back => {
+                        app_state.set(AppState::MainMenu);
+                    }
+                }
+                *bg_color = BackgroundColor(BUTTON_COLOR_PRESSED);
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(BUTTON_COLOR_HOVER);
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(BUTTON_COLOR_IDLE);
+            }
+        }
+    }
+}
+
+fn update_histogram(
+    mut commands: Commands,
+    stats: Res<Stats>,
+    view: Res<StatsView>,
+    settings: Res<UserSettings>,
+    list_query: Single<(Entity, Ref<HistogramList>)>,
+    button_query: Query<(&StatsOption, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    let (list, marker) = list_query.into_inner();
+    if !view.is_changed() && !marker.is_added() {
+        return;
+    }
+
+    for (option, children) in &button_query {
+        if *option != StatsOption::Difficulty {
+            continue;
+        }
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                let difficulty = settings.language.translate(view.difficulty.phrase());
+                text.0 = settings.language.fill(Phrase::Scores, &[&difficulty]);
+            }
+        }
+    }
+
+    let histogram = stats.histogram(view.difficulty);
+    let most = histogram.iter().copied().max().unwrap_or(0).max(1);
+
+    commands.entity(list).despawn_related::<Children>();
+    commands.entity(list).with_children(|parent| {
+        for (bucket, count) in histogram.into_iter().enumerate() {
+            let low = bucket as u32 * HISTOGRAM_BUCKET_SIZE;
+            let label = if bucket == HISTOGRAM_BUCKETS - 1 {
+                format!("{low}+")
+            } else {
+                format!("{low}-{}", low + HISTOGRAM_BUCKET_SIZE - 1)
+            };
+
+            parent.spawn((
+                Node {
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(6.0),
+                    ..default()
+                },
+                children![
+                    (
+                        Node {
+                            width: Val::Px(40.0),
+                            ..default()
+                        },
+                        Text(label),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(TEXT_COLOR),
+                    ),
+                    (
+                        Node {
+                            width: Val::Px(HISTOGRAM_BAR_WIDTH * count as f32 / most as f32),
+                            height: Val::Px(12.0),
+                            ..default()
+                        },
+                        BackgroundColor(BAR_COLOR),
+                    ),
+                    (
+                        Text(count.to_string()),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor(TEXT_COLOR),
+                    ),
+                ],
+            ));
+        }
+    });
+}
+
+fn format_duration(seconds: f64) -> String {
+    let seconds = seconds as u64;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes:02}m")
+    } else {
+        format!("{minutes}m {seconds:02}s")
+    }
+}
@@ -0,0 +1,135 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use bevy::prelude::*;
+use serde::{Serialize, de::DeserializeOwned};
+
+const APP_DIR: &str = "bevy-flappy";
+const PROFILES_DIR: &str = "profiles";
+const TMP_SUFFIX: &str = ".tmp";
+const BACKUP_SUFFIX: &str = ".bak";
+
+pub fn data_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_DIR)
+}
+
+pub fn data_path(relative: impl AsRef<Path>) -> PathBuf {
+    data_dir().join(relative)
+}
+
+// Where a file that belongs to a single player profile is kept
+pub fn profile_path(profile: &str, file: &str) -> PathBuf {
+    Path::new(PROFILES_DIR).join(profile).join(file)
+}
+
+pub fn remove_profile(profile: &str) -> io::Result<()> {
+    let dir = data_path(PROFILES_DIR).join(profile);
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    Ok(())
+}
+
+// Moves a file and its backup, if it exists, to a new place in the data directory
+pub fn relocate(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<()> {
+    let (from, to) = (data_path(from), data_path(to));
+    if !from.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let backup = with_suffix(&from, BACKUP_SUFFIX);
+    if backup.exists() {
+        fs::rename(backup, with_suffix(&to, BACKUP_SUFFIX))?;
+    }
+    fs::rename(from, to)
+}
+
+// Deletes a file and its backup, if they exist
+pub fn remove(relative: impl AsRef<Path>) -> io::Result<()> {
+    let path = data_path(relative);
+    for path in [with_suffix(&path, BACKUP_SUFFIX), path] {
+        match fs::remove_file(path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+// Falls back to the backup of the last good save if the file is missing or corrupt
+pub fn load<T: DeserializeOwned>(relative: impl AsRef<Path>) -> Option<T> {
+    let path = data_path(relative);
+    read(&path).or_else(|| read(&with_suffix(&path, BACKUP_SUFFIX)))
+}
+
+fn read<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let contents = fs::read_to_string(path).ok()?;
+
+    match ron::from_str(&contents) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            warn!("Failed to parse {}: {err}", path.display());
+            None
+        }
+    }
+}
+
+// Writes to a temporary file first and renames it into place, so a crash mid-write
+// can't leave a truncated file behind. The previous version is kept as a backup.
+pub fn save<T: Serialize>(relative: impl AsRef<Path>, value: &T) -> io::Result<()> {
+    let path = data_path(relative);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let contents = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(io::Error::other)?;
+    let tmp_path = with_suffix(&path, TMP_SUFFIX);
+    {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+    }
+
+    if path.exists() {
+        fs::copy(&path, with_suffix(&path, BACKUP_SUFFIX))?;
+    }
+    fs::rename(tmp_path, path)
+}
+
+// Adds a line to the end of a log file, such as the run history
+pub fn append_line(relative: impl AsRef<Path>, line: &str) -> io::Result<()> {
+    let path = data_path(relative);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{line}")
+}
+
+// Lines of a log file, or none if it doesn't exist yet
+pub fn read_lines(relative: impl AsRef<Path>) -> io::Result<Vec<String>> {
+    match fs::read_to_string(data_path(relative)) {
+        Ok(contents) => Ok(contents.lines().map(str::to_string).collect()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err),
+    }
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
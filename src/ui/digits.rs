@@ -0,0 +1,200 @@
+use bevy::prelude::*;
+
+// Native height of the digit sprites
+const LARGE_DIGIT_HEIGHT: f32 = 36.0;
+const SMALL_DIGIT_HEIGHT: f32 = 18.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DigitSize {
+    #[default]
+    Large,
+    Small,
+}
+
+impl DigitSize {
+    pub fn height(&self) -> f32 {
+        match self {
+            Self::Large => LARGE_DIGIT_HEIGHT,
+            Self::Small => SMALL_DIGIT_HEIGHT,
+        }
+    }
+
+    // Font size of the text fallback and the prefix
+    pub fn font_size(&self) -> f32 {
+        match self {
+            Self::Large => 50.0,
+            Self::Small => 26.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DigitAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+// Number drawn with the digit sprites, or as text until they are available
+#[derive(Component, Clone, Debug)]
+#[require(Node)]
+pub struct DigitScore {
+    pub value: u32,
+    pub size: DigitSize,
+    pub align: DigitAlign,
+    // Gap between digits in pixels
+    pub spacing: f32,
+    pub color: Color,
+    // Text in front of the number, such as a player tag
+    pub prefix: String,
+}
+
+impl DigitScore {
+    pub fn new(value: u32, size: DigitSize) -> Self {
+        Self {
+            value,
+            size,
+            align: DigitAlign::default(),
+            spacing: 2.0,
+            color: Color::WHITE,
+            prefix: String::new(),
+        }
+    }
+
+    pub fn with_align(mut self, align: DigitAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+}
+
+#[derive(Resource)]
+pub struct DigitImages {
+    pub digits: [Handle<Image>; 10],
+    // Set once every digit has loaded
+    pub ready: bool,
+}
+
+impl FromWorld for DigitImages {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self {
+            digits: std::array::from_fn(|digit| asset_server.load(format!("sprites/{digit}.png"))),
+            ready: false,
+        }
+    }
+}
+
+pub struct DigitsPlugin;
+
+impl Plugin for DigitsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DigitImages>()
+            .add_systems(Update, (check_digit_images, render_digits).chain());
+    }
+}
+
+// A digit that fails to load leaves every score on the text fallback
+fn check_digit_images(asset_server: Res<AssetServer>, mut digit_images: ResMut<DigitImages>) {
+    if !digit_images.ready
+        && digit_images
+            .digits
+            .iter()
+            .all(|handle| asset_server.is_loaded(handle))
+    {
+        digit_images.ready = true;
+    }
+}
+
+fn render_digits(
+    mut commands: Commands,
+    digit_images: Res<DigitImages>,
+    images: Res<Assets<Image>>,
+    mut score_query: Query<(Entity, Ref<DigitScore>, &mut Node)>,
+) {
+    for (entity, score, mut node) in &mut score_query {
+        if !score.is_changed() && !digit_images.is_changed() {
+            continue;
+        }
+
+        node.flex_direction = FlexDirection::Row;
+        node.align_items = AlignItems::Center;
+        node.column_gap = Val::Px(score.spacing);
+        node.justify_content = match score.align {
+            DigitAlign::Left => JustifyContent::FlexStart,
+            DigitAlign::Center => JustifyContent::Center,
+            DigitAlign::Right => JustifyContent::FlexEnd,
+        };
+
+        let text_font = TextFont {
+            font_size: score.size.font_size(),
+            ..default()
+        };
+        let text_shadow = TextShadow {
+            offset: Vec2::splat(0.8),
+            color: Color::BLACK,
+        };
+
+        commands.entity(entity).despawn_related::<Children>();
+        commands.entity(entity).with_children(|parent| {
+            if !digit_images.ready {
+                parent.spawn((
+                    Text(format!("{}{}", score.prefix, score.value)),
+                    text_font,
+                    TextColor(score.color),
+                    text_shadow,
+                ));
+                return;
+            }
+
+            if !score.prefix.is_empty() {
+                parent.spawn((
+                    Text(score.prefix.clone()),
+                    text_font,
+                    TextColor(score.color),
+                    text_shadow,
+                ));
+            }
+
+            let height = score.size.height();
+            for digit in score
+                .value
+                .to_string()
+                .bytes()
+                .map(|c| usize::from(c - b'0'))
+            {
+                let handle = &digit_images.digits[digit];
+                let aspect = images
+                    .get(handle)
+                    .map_or(2.0 / 3.0, |image| image.aspect_ratio().ratio());
+                parent.spawn((
+                    Node {
+                        width: Val::Px(height * aspect),
+                        height: Val::Px(height),
+                        ..default()
+                    },
+                    ImageNode {
+                        image: handle.clone(),
+                        color: score.color,
+                        ..default()
+                    },
+                ));
+            }
+        });
+    }
+}
@@ -1 +1,5 @@
 pub mod button;
+pub mod digits;
+pub mod navigation;
+pub mod toast;
+pub mod widgets;
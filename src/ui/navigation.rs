@@ -0,0 +1,117 @@
+use bevy::{prelude::*, ui::UiSystem};
+
+use crate::input::{ActionState, GameAction};
+
+// Button that keyboard and gamepad navigation currently points at
+#[derive(Resource, Default)]
+pub struct FocusedButton {
+    pub entity: Option<Entity>,
+    // Set while a screen reads raw input itself, such as when rebinding controls
+    pub locked: bool,
+    activated: Option<Entity>,
+}
+
+// Button that uses left and right itself, such as a slider, so only up and down move focus
+#[derive(Component, Default)]
+pub struct HorizontalControl;
+
+pub struct NavigationPlugin;
+
+impl Plugin for NavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FocusedButton>()
+            .add_systems(PreUpdate, navigate_buttons.after(UiSystem::Focus));
+    }
+}
+
+// Drives the same `Interaction` the mouse does, so every menu handler works unchanged
+fn navigate_buttons(
+    actions: Res<ActionState>,
+    mut focused: ResMut<FocusedButton>,
+    mut button_query: Query<
+        (
+            Entity,
+            &mut Interaction,
+            &GlobalTransform,
+            &InheritedVisibility,
+        ),
+        With<Button>,
+    >,
+    horizontal_query: Query<(), With<HorizontalControl>>,
+) {
+    // Release the button pressed on the previous frame
+    if let Some(entity) = focused.activated.take()
+        && let Ok((_, mut interaction, _, _)) = button_query.get_mut(entity)
+    {
+        interaction.set_if_neq(Interaction::None);
+    }
+
+    if focused.locked {
+        return;
+    }
+
+    let mut buttons: Vec<(Entity, Vec2)> = button_query
+        .iter()
+        .filter(|(_, _, _, visibility)| visibility.get())
+        .map(|(entity, _, transform, _)| (entity, transform.translation().truncate()))
+        .collect();
+    // Reading order: top to bottom, then left to right
+    buttons.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+
+    let current = focused
+        .entity
+        .and_then(|entity| buttons.iter().position(|(button, _)| *button == entity));
+    if current.is_none() {
+        focused.entity = None;
+    }
+    if buttons.is_empty() {
+        return;
+    }
+
+    let horizontal = current.is_some_and(|index| horizontal_query.contains(buttons[index].0));
+    let step = if actions.just_pressed(GameAction::NavigateUp)
+        || (!horizontal && actions.just_pressed(GameAction::NavigateLeft))
+    {
+        Some(-1)
+    } else if actions.just_pressed(GameAction::NavigateDown)
+        || (!horizontal && actions.just_pressed(GameAction::NavigateRight))
+    {
+        Some(1)
+    } else {
+        None
+    };
+
+    let next = match (current, step) {
+        (Some(index), Some(step)) => {
+            Some((index as isize + step).rem_euclid(buttons.len() as isize) as usize)
+        }
+        (None, Some(_)) => Some(0),
+        // Confirm without a focused button only focuses the first one
+        (None, None) if actions.just_pressed(GameAction::Confirm) => Some(0),
+        (current, None) => current,
+    };
+
+    let Some(index) = next else {
+        return;
+    };
+    let entity = buttons[index].0;
+    let confirmed = current.is_some() && actions.just_pressed(GameAction::Confirm);
+    focused.entity = Some(entity);
+
+    if let Ok((_, mut interaction, _, _)) = button_query.get_mut(entity) {
+        if confirmed {
+            *interaction = Interaction::Pressed;
+            focused.activated = Some(entity);
+        } else if *interaction == Interaction::None {
+            *interaction = Interaction::Hovered;
+        }
+    }
+
+    // Drop the highlight from a button that lost focus
+    if let Some(previous) = current.map(|index| buttons[index].0)
+        && previous != entity
+        && let Ok((_, mut interaction, _, _)) = button_query.get_mut(previous)
+    {
+        interaction.set_if_neq(Interaction::None);
+    }
+}
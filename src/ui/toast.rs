@@ -0,0 +1,113 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy_flappy_macros::hex_to_color;
+
+const TOAST_BG_COLOR: Color = hex_to_color!("#2f3e46");
+const TOAST_TITLE_COLOR: Color = hex_to_color!("#ffd54f");
+const TOAST_DURATION: f32 = 3.0;
+const TOAST_SLIDE: f32 = 0.3;
+const TOAST_TOP: f32 = 10.0;
+const TOAST_HIDDEN_TOP: f32 = -70.0;
+
+// Short notice shown at the top of the screen whatever state the app is in
+#[derive(Event, Clone, Debug)]
+pub struct Toast {
+    pub title: String,
+    pub message: String,
+}
+
+#[derive(Resource, Default)]
+pub struct ToastQueue(pub VecDeque<Toast>);
+
+#[derive(Component)]
+pub struct ToastNode {
+    pub timer: Timer,
+}
+
+pub struct ToastPlugin;
+
+impl Plugin for ToastPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Toast>()
+            .init_resource::<ToastQueue>()
+            .add_systems(Update, (queue_toasts, show_toast, animate_toast).chain());
+    }
+}
+
+fn queue_toasts(mut toasts: EventReader<Toast>, mut queue: ResMut<ToastQueue>) {
+    queue.0.extend(toasts.read().cloned());
+}
+
+// Toasts are shown one at a time, in the order they were sent
+fn show_toast(
+    mut commands: Commands,
+    mut queue: ResMut<ToastQueue>,
+    toast_query: Query<(), With<ToastNode>>,
+) {
+    if !toast_query.is_empty() {
+        return;
+    }
+    let Some(toast) = queue.0.pop_front() else {
+        return;
+    };
+
+    commands.spawn((
+        ToastNode {
+            timer: Timer::from_seconds(TOAST_DURATION, TimerMode::Once),
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(TOAST_HIDDEN_TOP),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        GlobalZIndex(100),
+        children![(
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+                ..default()
+            },
+            BorderRadius::all(Val::Px(8.0)),
+            BackgroundColor(TOAST_BG_COLOR.with_alpha(0.9)),
+            children![
+                (
+                    Text(toast.title),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(TOAST_TITLE_COLOR),
+                ),
+                (
+                    Text(toast.message),
+                    TextFont {
+                        font_size: 20.0,
+                        ..default()
+                    },
+                ),
+            ],
+        )],
+    ));
+}
+
+// Slides in from the top, waits, then slides back out
+fn animate_toast(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toast_query: Query<(Entity, &mut ToastNode, &mut Node)>,
+) {
+    for (entity, mut toast, mut node) in &mut toast_query {
+        if toast.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let elapsed = toast.timer.elapsed_secs();
+        let shown = (elapsed.min(TOAST_DURATION - elapsed) / TOAST_SLIDE).clamp(0.0, 1.0);
+        node.top = Val::Px(TOAST_HIDDEN_TOP.lerp(TOAST_TOP, shown));
+    }
+}
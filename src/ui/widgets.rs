@@ -0,0 +1,377 @@
+use bevy::{prelude::*, ui::RelativeCursorPosition};
+use bevy_flappy_macros::hex_to_color;
+
+use crate::input::{ActionState, GameAction};
+use crate::ui::navigation::{FocusedButton, HorizontalControl};
+
+const TRACK_COLOR: Color = hex_to_color!("#c3d8d2");
+const FILL_COLOR: Color = hex_to_color!("#84a98c");
+const KNOB_ON_COLOR: Color = hex_to_color!("#52796f");
+const KNOB_OFF_COLOR: Color = hex_to_color!("#f4f5f4");
+const POPUP_BG_COLOR: Color = hex_to_color!("#f4f5f4");
+const OPTION_HOVER_COLOR: Color = hex_to_color!("#c3d8d2");
+const TEXT_COLOR: Color = hex_to_color!("#2f3e46");
+
+const WIDGET_HEIGHT: f32 = 24.0;
+const KNOB_SIZE: f32 = 16.0;
+const FONT_SIZE: f32 = 14.0;
+
+// Value between 0 and 1, dragged with the mouse or stepped with left and right
+#[derive(Component, Clone, Debug)]
+#[require(Button, RelativeCursorPosition, HorizontalControl)]
+pub struct Slider {
+    pub value: f32,
+    pub step: f32,
+}
+
+#[derive(Component)]
+pub struct SliderFill;
+
+#[derive(Component)]
+pub struct SliderText;
+
+// On/off switch
+#[derive(Component, Clone, Debug)]
+#[require(Button)]
+pub struct Toggle {
+    pub on: bool,
+}
+
+#[derive(Component)]
+pub struct ToggleKnob;
+
+// One choice out of a list that opens below the widget
+#[derive(Component, Clone, Debug)]
+#[require(Button)]
+pub struct Dropdown {
+    pub options: Vec<String>,
+    pub selected: usize,
+}
+
+#[derive(Component)]
+pub struct DropdownText;
+
+#[derive(Component)]
+pub struct DropdownPopup;
+
+#[derive(Component)]
+pub struct DropdownOption {
+    pub dropdown: Entity,
+    pub index: usize,
+}
+
+pub struct WidgetsPlugin;
+
+impl Plugin for WidgetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                (drag_sliders, step_focused_slider, update_sliders).chain(),
+                (press_toggles, update_toggles).chain(),
+                (press_dropdowns, choose_dropdown_option, update_dropdowns).chain(),
+            ),
+        );
+    }
+}
+
+pub fn slider(value: f32, step: f32) -> impl Bundle {
+    (
+        Slider { value, step },
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Px(WIDGET_HEIGHT),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BorderRadius::all(Val::Px(6.0)),
+        BackgroundColor(TRACK_COLOR),
+        children![
+            (
+                SliderFill,
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.0),
+                    width: Val::Percent(value * 100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BorderRadius::all(Val::Px(6.0)),
+                BackgroundColor(FILL_COLOR),
+            ),
+            (
+                SliderText,
+                Text::default(),
+                TextFont {
+                    font_size: FONT_SIZE,
+                    ..default()
+                },
+                TextColor(TEXT_COLOR),
+            ),
+        ],
+    )
+}
+
+pub fn toggle(on: bool) -> impl Bundle {
+    (
+        Toggle { on },
+        Node {
+            width: Val::Px(WIDGET_HEIGHT * 2.0),
+            height: Val::Px(WIDGET_HEIGHT),
+            padding: UiRect::horizontal(Val::Px(4.0)),
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BorderRadius::all(Val::Px(WIDGET_HEIGHT / 2.0)),
+        BackgroundColor(TRACK_COLOR),
+        children![(
+            ToggleKnob,
+            Node {
+                width: Val::Px(KNOB_SIZE),
+                height: Val::Px(KNOB_SIZE),
+                ..default()
+            },
+            BorderRadius::all(Val::Px(KNOB_SIZE / 2.0)),
+            BackgroundColor(KNOB_OFF_COLOR),
+        )],
+    )
+}
+
+pub fn dropdown(options: Vec<String>, selected: usize) -> impl Bundle {
+    (
+        Dropdown { options, selected },
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Px(WIDGET_HEIGHT),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BorderRadius::all(Val::Px(6.0)),
+        BackgroundColor(TRACK_COLOR),
+        children![(
+            DropdownText,
+            Text::default(),
+            TextFont {
+                font_size: FONT_SIZE,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+        )],
+    )
+}
+
+// Only a held mouse button drags, so confirming a focused slider doesn't move it
+fn drag_sliders(
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut slider_query: Query<(&Interaction, &RelativeCursorPosition, &mut Slider)>,
+) {
+    if !mouse.pressed(MouseButton::Left) {
+        return;
+    }
+
+    for (interaction, cursor, mut slider) in &mut slider_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(position) = cursor.normalized else {
+            continue;
+        };
+
+        let value = (position.x.clamp(0.0, 1.0) * 100.0).round() / 100.0;
+        if slider.value != value {
+            slider.value = value;
+        }
+    }
+}
+
+fn step_focused_slider(
+    actions: Res<ActionState>,
+    focused: Res<FocusedButton>,
+    mut slider_query: Query<&mut Slider>,
+) {
+    let Some(mut slider) = focused
+        .entity
+        .and_then(|entity| slider_query.get_mut(entity).ok())
+    else {
+        return;
+    };
+
+    let direction = if actions.just_pressed(GameAction::NavigateRight) {
+        1.0
+    } else if actions.just_pressed(GameAction::NavigateLeft) {
+        -1.0
+    } else {
+        return;
+    };
+    let steps = (slider.value / slider.step).round() + direction;
+    slider.value = (steps * slider.step).clamp(0.0, 1.0);
+}
+
+fn update_sliders(
+    slider_query: Query<(&Slider, &Children), Changed<Slider>>,
+    mut fill_query: Query<&mut Node, With<SliderFill>>,
+    mut text_query: Query<&mut Text, With<SliderText>>,
+) {
+    for (slider, children) in &slider_query {
+        for child in children.iter() {
+            if let Ok(mut node) = fill_query.get_mut(child) {
+                node.width = Val::Percent(slider.value * 100.0);
+            }
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0 = format!("{:.0}%", slider.value * 100.0);
+            }
+        }
+    }
+}
+
+fn press_toggles(mut toggle_query: Query<(&Interaction, &mut Toggle), Changed<Interaction>>) {
+    for (interaction, mut toggle) in &mut toggle_query {
+        if *interaction == Interaction::Pressed {
+            toggle.on = !toggle.on;
+        }
+    }
+}
+
+fn update_toggles(
+    mut toggle_query: Query<(&Toggle, &Children, &mut Node), Changed<Toggle>>,
+    mut knob_query: Query<&mut BackgroundColor, With<ToggleKnob>>,
+) {
+    for (toggle, children, mut node) in &mut toggle_query {
+        // The knob sits on the right when the switch is on
+        node.justify_content = if toggle.on {
+            JustifyContent::FlexEnd
+        } else {
+            JustifyContent::FlexStart
+        };
+        for child in children.iter() {
+            if let Ok(mut color) = knob_query.get_mut(child) {
+                color.0 = if toggle.on {
+                    KNOB_ON_COLOR
+                } else {
+                    KNOB_OFF_COLOR
+                };
+            }
+        }
+    }
+}
+
+// Pressing a dropdown opens its list, or closes it if it's already open
+fn press_dropdowns(
+    mut commands: Commands,
+    dropdown_query: Query<(Entity, &Interaction, &Dropdown), Changed<Interaction>>,
+    popup_query: Query<(Entity, &ChildOf), With<DropdownPopup>>,
+) {
+    for (entity, interaction, dropdown) in &dropdown_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let mut was_open = false;
+        for (popup, parent) in &popup_query {
+            was_open |= parent.parent() == entity;
+            commands.entity(popup).despawn();
+        }
+        if was_open {
+            continue;
+        }
+
+        commands.entity(entity).with_children(|parent| {
+            parent
+                .spawn((
+                    DropdownPopup,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        top: Val::Percent(100.0),
+                        left: Val::Px(0.0),
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    GlobalZIndex(10),
+                    BackgroundColor(POPUP_BG_COLOR),
+                    BoxShadow::new(
+                        Color::BLACK.with_alpha(0.4),
+                        Val::Px(0.0),
+                        Val::Px(2.0),
+                        Val::Px(0.0),
+                        Val::Px(4.0),
+                    ),
+                ))
+                .with_children(|popup| {
+                    for (index, option) in dropdown.options.iter().enumerate() {
+                        popup.spawn((
+                            Button,
+                            DropdownOption {
+                                dropdown: entity,
+                                index,
+                            },
+                            Node {
+                                height: Val::Px(WIDGET_HEIGHT),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor(POPUP_BG_COLOR),
+                            children![(
+                                Text(option.clone()),
+                                TextFont {
+                                    font_size: FONT_SIZE,
+                                    ..default()
+                                },
+                                TextColor(TEXT_COLOR),
+                            )],
+                        ));
+                    }
+                });
+        });
+    }
+}
+
+fn choose_dropdown_option(
+    mut commands: Commands,
+    mut option_query: Query<
+        (
+            &Interaction,
+            &DropdownOption,
+            &ChildOf,
+            &mut BackgroundColor,
+        ),
+        Changed<Interaction>,
+    >,
+    mut dropdown_query: Query<&mut Dropdown>,
+) {
+    for (interaction, option, popup, mut color) in &mut option_query {
+        match *interaction {
+            Interaction::Pressed => {
+                if let Ok(mut dropdown) = dropdown_query.get_mut(option.dropdown)
+                    && dropdown.selected != option.index
+                {
+                    dropdown.selected = option.index;
+                }
+                commands.entity(popup.parent()).despawn();
+            }
+            Interaction::Hovered => color.0 = OPTION_HOVER_COLOR,
+            Interaction::None => color.0 = POPUP_BG_COLOR,
+        }
+    }
+}
+
+fn update_dropdowns(
+    dropdown_query: Query<(&Dropdown, &Children), Changed<Dropdown>>,
+    mut text_query: Query<&mut Text, With<DropdownText>>,
+) {
+    for (dropdown, children) in &dropdown_query {
+        let label = dropdown
+            .options
+            .get(dropdown.selected)
+            .cloned()
+            .unwrap_or_default();
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.0.clone_from(&label);
+            }
+        }
+    }
+}